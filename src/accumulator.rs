@@ -0,0 +1,105 @@
+//! A wrapper for accumulating sums of curve points.
+
+use core::ops::{AddAssign, SubAssign};
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::Curve;
+
+/// An accumulator for sums of elliptic curve points, that keeps the running sum in the
+/// efficient representation of the curve.
+///
+/// Points can only be added to and subtracted from the accumulator; the running sum is
+/// only normalized when it is observed with [`Accumulator::to_affine`]. Protocol code
+/// that accumulates into an `Accumulator` rather than an affine point therefore cannot
+/// accidentally pay for a normalization after every addition.
+#[derive(Clone, Copy, Debug)]
+pub struct Accumulator<C: Curve> {
+    sum: C,
+}
+
+impl<C: Curve> Default for Accumulator<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Curve> Accumulator<C> {
+    /// Constructs an accumulator whose sum is the identity.
+    pub fn new() -> Self {
+        Accumulator { sum: C::identity() }
+    }
+
+    /// Adds a point in affine form to the accumulated sum, using mixed addition.
+    pub fn add_affine(&mut self, point: &C::AffineRepr) {
+        self.sum += point;
+    }
+
+    /// Subtracts a point in affine form from the accumulated sum, using mixed addition.
+    pub fn sub_affine(&mut self, point: &C::AffineRepr) {
+        self.sum -= point;
+    }
+
+    /// Returns the accumulated sum, converted to its affine representation.
+    pub fn to_affine(&self) -> C::AffineRepr {
+        self.sum.to_affine()
+    }
+
+    /// Consumes the accumulator, returning the accumulated sum.
+    pub fn into_inner(self) -> C {
+        self.sum
+    }
+}
+
+impl<C: Curve> AddAssign<C> for Accumulator<C> {
+    fn add_assign(&mut self, rhs: C) {
+        self.sum += rhs;
+    }
+}
+
+impl<'a, C: Curve> AddAssign<&'a C> for Accumulator<C> {
+    fn add_assign(&mut self, rhs: &'a C) {
+        self.sum += rhs;
+    }
+}
+
+impl<C: Curve> SubAssign<C> for Accumulator<C> {
+    fn sub_assign(&mut self, rhs: C) {
+        self.sum -= rhs;
+    }
+}
+
+impl<'a, C: Curve> SubAssign<&'a C> for Accumulator<C> {
+    fn sub_assign(&mut self, rhs: &'a C) {
+        self.sum -= rhs;
+    }
+}
+
+impl<C: Curve> Extend<C> for Accumulator<C> {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        for p in iter {
+            self.sum += p;
+        }
+    }
+}
+
+impl<'a, C: Curve> Extend<&'a C> for Accumulator<C> {
+    fn extend<I: IntoIterator<Item = &'a C>>(&mut self, iter: I) {
+        for p in iter {
+            self.sum += p;
+        }
+    }
+}
+
+impl<C: Curve> PartialEq for Accumulator<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sum == other.sum
+    }
+}
+
+impl<C: Curve> Eq for Accumulator<C> {}
+
+impl<C: Curve> ConstantTimeEq for Accumulator<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.sum.ct_eq(&other.sum)
+    }
+}
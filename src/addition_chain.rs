@@ -0,0 +1,84 @@
+//! Multiplication by small fixed constants with addition chains.
+//!
+//! Curve arithmetic multiplies by the same constants over and over: cofactors, the BLS
+//! parameter `x`, `3b` in doubling formulas. These constants often have structure (long
+//! runs of zero bits, or a sparse signed-binary form) that a short, hand-picked chain of
+//! doublings and additions exploits far better than a generic scalar multiplication.
+//!
+//! Curves describe such a chain once by implementing [`AdditionChain`], and then
+//! multiply by the constant with [`AdditionChain::mul`].
+
+use crate::Group;
+
+/// A step of an [`AdditionChain`], updating an accumulator `A` given the base `P`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainStep {
+    /// Doubles the accumulator the given number of times: `A = [2^n] A`.
+    Double(u32),
+    /// Adds the base to the accumulator: `A = A + P`.
+    Add,
+    /// Subtracts the base from the accumulator: `A = A - P`.
+    Sub,
+}
+
+/// A signed-binary addition chain computing `[k] P` for a fixed positive constant `k`.
+///
+/// The accumulator starts at `P`, and [`AdditionChain::STEPS`] are applied to it in
+/// order. For example, `k = 15 = 16 - 1` is computed by:
+///
+/// ```
+/// use group::addition_chain::{AdditionChain, ChainStep};
+///
+/// struct Fifteen;
+///
+/// impl AdditionChain for Fifteen {
+///     const STEPS: &'static [ChainStep] = &[ChainStep::Double(4), ChainStep::Sub];
+/// }
+///
+/// assert_eq!(Fifteen::constant(), 15);
+/// ```
+pub trait AdditionChain {
+    /// The steps of the chain.
+    const STEPS: &'static [ChainStep];
+
+    /// Returns the constant `k` computed by this chain.
+    ///
+    /// This is intended for checking chains in tests. It panics if the constant does not
+    /// fit in a `u128`, or if an intermediate value is negative.
+    fn constant() -> u128 {
+        Self::STEPS.iter().fold(1u128, |acc, step| match *step {
+            ChainStep::Double(n) => acc
+                .checked_mul(1u128.checked_shl(n).expect("too many doublings"))
+                .expect("constant overflows u128"),
+            ChainStep::Add => acc.checked_add(1).expect("constant overflows u128"),
+            ChainStep::Sub => acc.checked_sub(1).expect("intermediate value is negative"),
+        })
+    }
+
+    /// Computes `[k] p`.
+    ///
+    /// This runs in constant time with respect to `p`; the sequence of operations only
+    /// depends on the (public) chain.
+    fn mul<G: Group>(p: &G) -> G {
+        let mut acc = *p;
+        for step in Self::STEPS {
+            match *step {
+                ChainStep::Double(n) => {
+                    for _ in 0..n {
+                        acc = acc.double();
+                    }
+                    trace!(DOUBLINGS, n as usize);
+                }
+                ChainStep::Add => {
+                    acc += p;
+                    trace!(ADDITIONS);
+                }
+                ChainStep::Sub => {
+                    acc -= p;
+                    trace!(ADDITIONS);
+                }
+            }
+        }
+        acc
+    }
+}
@@ -0,0 +1,58 @@
+//! Relationships between the fields of curves that form a 2-chain.
+
+use ff::PrimeField;
+
+use crate::{coordinates::AffineCoordinates, prime::PrimeCurve, GroupCore};
+
+/// The scalar field of the outer curve of the 2-chain that `A` belongs to.
+pub type OuterScalar<A> = <<A as Chain>::Outer as GroupCore>::Scalar;
+
+/// The affine representation of an "inner" curve whose base field is the scalar field of
+/// an "outer" curve, such as BLS12-377 and BW6-761.
+///
+/// Arithmetic on inner curve points can then be expressed natively in circuits over the
+/// outer curve's scalar field, which is what recursive proof systems built on 2-chains
+/// rely on. The two fields are usually distinct types in their respective curve crates,
+/// so this trait provides the conversions between them.
+pub trait Chain: AffineCoordinates<Base = <Self as Chain>::InnerBase> {
+    /// The base field of the inner curve.
+    type InnerBase: PrimeField;
+
+    /// The outer curve, whose scalar field has the same modulus as
+    /// [`Chain::InnerBase`].
+    type Outer: PrimeCurve;
+
+    /// Converts an inner curve base field element into an outer curve scalar.
+    ///
+    /// The default implementation re-encodes the element with [`PrimeField::to_repr`],
+    /// and so requires the two fields to use the same byte encoding; implementations
+    /// should override it otherwise.
+    fn base_to_outer_scalar(x: &Self::InnerBase) -> OuterScalar<Self> {
+        let mut repr = <OuterScalar<Self> as PrimeField>::Repr::default();
+        repr.as_mut().copy_from_slice(x.to_repr().as_ref());
+        OuterScalar::<Self>::from_repr(repr).expect("the fields of a 2-chain have the same modulus")
+    }
+
+    /// Converts an outer curve scalar into an inner curve base field element.
+    ///
+    /// The default implementation has the same requirements as
+    /// [`Chain::base_to_outer_scalar`].
+    fn outer_scalar_to_base(s: &OuterScalar<Self>) -> Self::InnerBase {
+        let mut repr = <Self::InnerBase as PrimeField>::Repr::default();
+        repr.as_mut().copy_from_slice(s.to_repr().as_ref());
+        Self::InnerBase::from_repr(repr).expect("the fields of a 2-chain have the same modulus")
+    }
+
+    /// Returns the coordinates `(x, y)` of this point as outer curve scalars, or `None`
+    /// if it is the identity.
+    ///
+    /// This is not constant time with respect to whether `self` is the identity.
+    fn coordinates_as_outer_scalars(&self) -> Option<(OuterScalar<Self>, OuterScalar<Self>)> {
+        Option::from(self.coordinates()).map(|(x, y)| {
+            (
+                Self::base_to_outer_scalar(&x),
+                Self::base_to_outer_scalar(&y),
+            )
+        })
+    }
+}
@@ -1,9 +1,12 @@
 use core::fmt;
-use core::ops::{Mul, Neg};
-use ff::PrimeField;
-use subtle::{Choice, CtOption};
+use core::ops::Neg;
+use ff::{Field, PrimeField};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
-use crate::{prime::PrimeGroup, Curve, Group, GroupEncoding, GroupOps, GroupOpsOwned};
+use crate::{
+    prime::PrimeGroup, AffineGroupOps, AffineGroupOpsOwned, AffineScalarMul, Curve, Group,
+    GroupCore, GroupEncoding, GroupOps, GroupOpsOwned,
+};
 
 /// This trait represents an element of a cryptographic group with a large prime-order
 /// subgroup and a comparatively-small cofactor.
@@ -28,6 +31,18 @@ pub trait CofactorGroup:
     /// If `Self` implements [`PrimeGroup`], this returns `self`.
     fn clear_cofactor(&self) -> Self::Subgroup;
 
+    /// Maps each element of `elements` to the prime-order subgroup, as with
+    /// [`CofactorGroup::clear_cofactor`].
+    ///
+    /// The default implementation clears the cofactor of each element independently.
+    /// Implementations may override it to share work between elements (for example by
+    /// batching normalizations), which matters when importing large numbers of untrusted
+    /// points.
+    #[cfg(feature = "alloc")]
+    fn batch_clear_cofactor(elements: &[Self]) -> alloc::vec::Vec<Self::Subgroup> {
+        elements.iter().map(Self::clear_cofactor).collect()
+    }
+
     /// Returns `self` if it is contained in the prime-order subgroup.
     ///
     /// If `Self` implements [`PrimeGroup`], this returns `Some(self)`.
@@ -50,6 +65,159 @@ pub trait CofactorGroup:
     /// - `false` if `self` has non-zero torsion component and is not in the prime-order
     ///   subgroup.
     fn is_torsion_free(&self) -> Choice;
+
+    /// Determines if this element is in the prime-order subgroup, assuming that it is a
+    /// valid point on the curve.
+    ///
+    /// This names the check that decoding routines need after an on-curve check. The
+    /// default implementation defers to [`CofactorGroup::is_torsion_free`].
+    /// Implementations for prime-order curves (where every point on the curve is in the
+    /// subgroup) should override it to return `true` without doing any work, and
+    /// implementations for curves with a cofactor may override it with a cheaper check
+    /// (such as one based on an endomorphism) that is only sound for points on the curve.
+    fn is_in_correct_subgroup_assuming_on_curve(&self) -> Choice {
+        self.is_torsion_free()
+    }
+}
+
+/// A [`CofactorGroup`] that knows the multiplier used by
+/// [`CofactorGroup::clear_cofactor`], and so can undo its effect on the prime-order
+/// subgroup.
+///
+/// `clear_cofactor` multiplies by some multiple `k·h` of the cofactor, as in the
+/// `clear_cofactor` of [RFC 9380], which moves elements that are already in the
+/// subgroup. Protocols that instead need a projection that fixes the subgroup can
+/// follow it with a multiplication by `(k·h)⁻¹ mod r`; both behaviours are then
+/// expressible for the same group.
+///
+/// [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html#name-clearing-the-cofactor
+pub trait CofactorInverse: CofactorGroup {
+    /// Returns the multiplier `k·h` applied by [`CofactorGroup::clear_cofactor`], reduced
+    /// modulo the order of the prime-order subgroup.
+    fn cofactor_multiplier() -> Self::Scalar;
+
+    /// Returns the inverse of [`CofactorInverse::cofactor_multiplier`] modulo the order of
+    /// the prime-order subgroup.
+    ///
+    /// The default implementation inverts the multiplier, which is invertible as the
+    /// cofactor is coprime to the order of the subgroup. Implementations may override it
+    /// with a precomputed constant.
+    fn cofactor_multiplier_inverse() -> Self::Scalar {
+        Self::cofactor_multiplier().invert().unwrap()
+    }
+
+    /// Multiplies an element of the prime-order subgroup by
+    /// [`CofactorInverse::cofactor_multiplier_inverse`].
+    fn scale_by_cofactor_inverse(element: &Self::Subgroup) -> Self::Subgroup {
+        *element * Self::cofactor_multiplier_inverse()
+    }
+
+    /// Maps `self` to the prime-order subgroup, as with [`CofactorGroup::clear_cofactor`],
+    /// but such that elements of the subgroup are mapped to themselves.
+    fn clear_cofactor_preserving_subgroup(&self) -> Self::Subgroup {
+        Self::scale_by_cofactor_inverse(&self.clear_cofactor())
+    }
+
+    /// Splits `self` into its component in the prime-order subgroup and its small-order
+    /// torsion component, which sum to `self`.
+    ///
+    /// The prime-order component is [`CofactorInverse::clear_cofactor_preserving_subgroup`],
+    /// and the torsion component is the remainder. This is useful for debugging
+    /// subgroup-confusion bugs, and for implementing cofactored verification equations.
+    fn decompose(&self) -> (Self::Subgroup, Self) {
+        let prime = self.clear_cofactor_preserving_subgroup();
+        let torsion = *self - prime;
+        (prime, torsion)
+    }
+}
+
+/// A [`CofactorGroup`] whose encodings can be accompanied by a hint that makes the
+/// subgroup check cheaper.
+///
+/// Verifiers ingesting many points spend most of their decoding time in subgroup checks.
+/// The encoder can often compute a short witness of subgroup membership (such as the
+/// decomposition used by an endomorphism-based check) that the decoder verifies much
+/// faster than a full check. The hint is transmitted alongside the usual encoding:
+///
+/// ```ignore
+/// let (bytes, hint) = p.to_bytes_with_hint();
+/// // ... send both ...
+/// let p = G1Projective::from_bytes_with_hint(&bytes, &hint).unwrap();
+/// ```
+///
+/// [`SubgroupHint::from_bytes_with_hint`] decodes with
+/// [`GroupEncoding::from_bytes_on_curve_only`], whose default implementation performs the
+/// full subgroup check. Implementors must override that method with a decoder that only
+/// checks the curve equation, or the hint saves nothing.
+pub trait SubgroupHint: CofactorGroup {
+    /// The encoding of a hint.
+    type Hint: Copy + Default + Send + Sync + 'static + AsRef<[u8]> + AsMut<[u8]>;
+
+    /// Computes the hint for this element.
+    ///
+    /// This is run by the encoder, and may be as expensive as a full subgroup check. The
+    /// result is unspecified if this element is not in the prime-order subgroup.
+    fn subgroup_hint(&self) -> Self::Hint;
+
+    /// Determines if this element is in the prime-order subgroup with the help of `hint`,
+    /// assuming that it is a valid point on the curve.
+    ///
+    /// This must be sound for every hint: it must return `false` for elements outside the
+    /// subgroup, whatever the hint. It may also return `false` for elements in the
+    /// subgroup if the hint is not the one returned by [`SubgroupHint::subgroup_hint`].
+    fn is_torsion_free_with_hint(&self, hint: &Self::Hint) -> Choice;
+
+    /// Converts this element into its byte encoding and its hint.
+    fn to_bytes_with_hint(&self) -> (Self::Repr, Self::Hint) {
+        (self.to_bytes(), self.subgroup_hint())
+    }
+
+    /// Attempts to deserialize an element from its byte encoding and hint, checking that
+    /// it is on the curve and using the hint to check that it is in the prime-order
+    /// subgroup.
+    ///
+    /// This provides the same guarantees as [`GroupEncoding::from_bytes`].
+    fn from_bytes_with_hint(bytes: &Self::Repr, hint: &Self::Hint) -> CtOption<Self>
+    where
+        Self: ConditionallySelectable + Default,
+    {
+        Self::from_bytes_on_curve_only(bytes)
+            .and_then(|p| CtOption::new(p, p.is_torsion_free_with_hint(hint)))
+    }
+}
+
+/// How [`verify_equation`] treats small-order components of the equation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationMode {
+    /// Accept only if the equation holds exactly.
+    Cofactorless,
+    /// Accept if the equation holds after multiplying it by the cofactor, ignoring any
+    /// small-order components.
+    Cofactored,
+}
+
+/// Determines if `A + [s] B + [t] C` is the identity, in the given mode.
+///
+/// Signature schemes over groups with a cofactor (such as Ed25519) disagree on whether
+/// verification equations should be checked exactly, or after multiplying them by the
+/// cofactor. The two only differ on inputs with small-order components, which honest
+/// signers never produce, but mixing them across implementations lets such inputs split
+/// consensus. Verifiers should pick one [`VerificationMode`] and use it everywhere.
+///
+/// This runs in constant time.
+pub fn verify_equation<G: CofactorGroup>(
+    a: &G,
+    s: &G::Scalar,
+    b: &G,
+    t: &G::Scalar,
+    c: &G,
+    mode: VerificationMode,
+) -> Choice {
+    let sum = *a + *b * s + *c * t;
+    match mode {
+        VerificationMode::Cofactorless => sum.is_identity(),
+        VerificationMode::Cofactored => sum.is_small_order(),
+    }
 }
 
 /// Efficient representation of an elliptic curve point guaranteed to be
@@ -57,15 +225,15 @@ pub trait CofactorGroup:
 pub trait CofactorCurve:
     Curve<AffineRepr = <Self as CofactorCurve>::Affine> + CofactorGroup
 {
-    type Affine: CofactorCurveAffine<Curve = Self, Scalar = Self::Scalar>
-        + Mul<Self::Scalar, Output = Self>
-        + for<'r> Mul<&'r Self::Scalar, Output = Self>;
+    type Affine: CofactorCurveAffine<Curve = Self, Scalar = Self::Scalar>;
 }
 
 /// Affine representation of an elliptic curve point guaranteed to be
 /// in the correct prime order subgroup.
-pub trait CofactorCurveAffine:
-    GroupEncoding
+///
+/// Points that may be derived from secret data should be compared with
+/// [`ConstantTimeEq::ct_eq`] rather than `==`.
+pub trait CofactorCurveAffine: GroupEncoding
     + Copy
     + Clone
     + Sized
@@ -74,13 +242,14 @@ pub trait CofactorCurveAffine:
     + fmt::Debug
     + PartialEq
     + Eq
+    + ConstantTimeEq
     + 'static
     + Neg<Output = Self>
-    + Mul<<Self as CofactorCurveAffine>::Scalar, Output = <Self as CofactorCurveAffine>::Curve>
-    + for<'r> Mul<
-        &'r <Self as CofactorCurveAffine>::Scalar,
-        Output = <Self as CofactorCurveAffine>::Curve,
-    >
+    + AffineScalarMul<<Self as CofactorCurveAffine>::Scalar, <Self as CofactorCurveAffine>::Curve>
+    + AffineGroupOps<Self, <Self as CofactorCurveAffine>::Curve>
+    + AffineGroupOpsOwned<Self, <Self as CofactorCurveAffine>::Curve>
+    + AffineGroupOps<<Self as CofactorCurveAffine>::Curve, <Self as CofactorCurveAffine>::Curve>
+    + AffineGroupOpsOwned<<Self as CofactorCurveAffine>::Curve, <Self as CofactorCurveAffine>::Curve>
 {
     type Scalar: PrimeField;
     type Curve: CofactorCurve<Affine = Self, Scalar = Self::Scalar>;
@@ -0,0 +1,60 @@
+//! Pedersen commitments over a generic [`Group`].
+
+use crate::Group;
+
+/// A pair of generators for committing to a single scalar value.
+///
+/// The security of the commitment scheme relies on nobody knowing the discrete
+/// logarithm of the blinding generator with respect to the value generator. Generators
+/// should therefore be derived independently, for example via hash-to-curve with distinct
+/// domain separation tags, rather than by multiplying one by a known scalar.
+#[derive(Clone, Copy, Debug)]
+pub struct PedersenGens<G: Group> {
+    value: G,
+    blinding: G,
+}
+
+impl<G: Group> PedersenGens<G> {
+    /// Constructs a set of Pedersen generators from a value generator and a blinding
+    /// generator.
+    pub fn new(value: G, blinding: G) -> Self {
+        PedersenGens { value, blinding }
+    }
+
+    /// Returns the generator that the committed value is multiplied by.
+    pub fn value_generator(&self) -> &G {
+        &self.value
+    }
+
+    /// Returns the generator that the blinding factor is multiplied by.
+    pub fn blinding_generator(&self) -> &G {
+        &self.blinding
+    }
+
+    /// Commits to `value` using the blinding factor `blinding`, returning
+    /// `value * V + blinding * B`.
+    pub fn commit(&self, value: &G::Scalar, blinding: &G::Scalar) -> G {
+        self.value * value + self.blinding * blinding
+    }
+}
+
+/// Commits to a vector of values using one generator per value, returning
+/// `sum(values[i] * value_gens[i]) + blinding * blinding_gen`. This function will panic
+/// if `value_gens.len() != values.len()`.
+///
+/// The values and blinding factor are usually secret, so each term is computed with the
+/// group's own scalar multiplication rather than with the variable-time multi-scalar
+/// multiplications in this crate.
+pub fn commit_vector<G: Group>(
+    value_gens: &[G],
+    values: &[G::Scalar],
+    blinding_gen: &G,
+    blinding: &G::Scalar,
+) -> G {
+    assert_eq!(value_gens.len(), values.len());
+
+    value_gens
+        .iter()
+        .zip(values.iter())
+        .fold(*blinding_gen * blinding, |acc, (g, v)| acc + *g * v)
+}
@@ -0,0 +1,219 @@
+//! Compact encodings for collections of points.
+
+use alloc::vec::Vec;
+use core::ops::Neg;
+use subtle::{Choice, CtOption};
+
+use crate::{GroupEncoding, XOnly};
+
+/// A vector of points compressed to their x-coordinates, along with a packed bitvector
+/// recording which of the points have an odd y-coordinate.
+///
+/// Compared to encoding each point independently, this avoids spending a whole byte (or
+/// a flag bit inside each encoding) on the parity of every point, which adds up for large
+/// structured reference strings and proofs.
+///
+/// Neither compression nor decompression is constant time; this type is intended for
+/// public data.
+#[derive(Clone, Debug)]
+pub struct CompressedBatch<P: XOnly> {
+    xs: Vec<P::XRepr>,
+    odd_y: Vec<u8>,
+}
+
+impl<P: XOnly + Neg<Output = P>> CompressedBatch<P> {
+    /// Compresses the given points.
+    ///
+    /// Whether the identity can be compressed depends on the [`XOnly`] implementation.
+    pub fn new(points: &[P]) -> Self {
+        let mut odd_y = vec![0; (points.len() + 7) / 8];
+        let xs = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                if !bool::from(p.has_even_y()) {
+                    odd_y[i / 8] |= 1 << (i % 8);
+                }
+                p.to_x_bytes()
+            })
+            .collect();
+
+        CompressedBatch { xs, odd_y }
+    }
+
+    /// Returns the number of points in this batch.
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    /// Returns `true` if this batch contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    /// Decompresses the points in this batch, returning `None` if any of them is
+    /// invalid.
+    ///
+    /// The points are deserialized with [`XOnly::batch_from_x_bytes_even_y`].
+    pub fn decompress(&self) -> Option<Vec<P>> {
+        P::batch_from_x_bytes_even_y(&self.xs)
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let p: Option<P> = p.into();
+                p.map(|p| if self.is_odd(i) { -p } else { p })
+            })
+            .collect()
+    }
+
+    /// Serializes this batch as the concatenation of the x-coordinate encodings, followed
+    /// by the parity bitvector (least-significant bit first).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::encoded_len(self.len()));
+        for x in &self.xs {
+            bytes.extend_from_slice(x.as_ref());
+        }
+        bytes.extend_from_slice(&self.odd_y);
+        bytes
+    }
+
+    /// Parses a batch from its serialization, returning `None` if `bytes` does not have
+    /// the length of a valid serialization, or if the padding bits of the parity
+    /// bitvector are not zero.
+    ///
+    /// The x-coordinates are not validated until [`CompressedBatch::decompress`] is
+    /// called.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let x_len = P::XRepr::default().as_ref().len();
+
+        // The encoded length n * x_len + ceil(n / 8) is strictly increasing in n, so at
+        // most one n can match. Inputs whose length in bits overflows are rejected.
+        let n = bytes.len().checked_mul(8)? / (8 * x_len + 1);
+        if Self::encoded_len(n) != bytes.len() {
+            return None;
+        }
+
+        let (xs_bytes, odd_y) = bytes.split_at(n * x_len);
+        if n % 8 != 0 && odd_y[n / 8] >> (n % 8) != 0 {
+            return None;
+        }
+
+        let xs = xs_bytes
+            .chunks(x_len)
+            .map(|chunk| {
+                let mut x = P::XRepr::default();
+                x.as_mut().copy_from_slice(chunk);
+                x
+            })
+            .collect();
+
+        Some(CompressedBatch {
+            xs,
+            odd_y: odd_y.to_vec(),
+        })
+    }
+
+    fn is_odd(&self, i: usize) -> bool {
+        (self.odd_y[i / 8] >> (i % 8)) & 1 == 1
+    }
+
+    fn encoded_len(n: usize) -> usize {
+        n * P::XRepr::default().as_ref().len() + (n + 7) / 8
+    }
+}
+
+/// A store of points kept in their compressed [`GroupEncoding`] in memory, and
+/// decompressed on demand.
+///
+/// This suits verifiers that hold very many points but only touch a few of them at a
+/// time. Once [`CompressedPoints::validate`] has succeeded (or if the store was built from
+/// valid points), subsequent accesses decompress with
+/// [`GroupEncoding::from_bytes_unchecked`] and so skip repeating the validation work.
+#[derive(Clone, Debug)]
+pub struct CompressedPoints<G: GroupEncoding> {
+    reprs: Vec<G::Repr>,
+    validated: bool,
+}
+
+impl<G: GroupEncoding> CompressedPoints<G> {
+    /// Constructs a store from untrusted encodings. Each access validates the point it
+    /// decompresses until [`CompressedPoints::validate`] is called.
+    pub fn new(reprs: Vec<G::Repr>) -> Self {
+        CompressedPoints {
+            reprs,
+            validated: false,
+        }
+    }
+
+    /// Constructs a store by compressing the given points.
+    pub fn from_points(points: &[G]) -> Self {
+        CompressedPoints {
+            reprs: points.iter().map(|p| p.to_bytes()).collect(),
+            validated: true,
+        }
+    }
+
+    /// Returns the number of points in this store.
+    pub fn len(&self) -> usize {
+        self.reprs.len()
+    }
+
+    /// Returns `true` if this store contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.reprs.is_empty()
+    }
+
+    /// Returns the encodings held by this store.
+    pub fn reprs(&self) -> &[G::Repr] {
+        &self.reprs
+    }
+
+    /// Returns `true` if every encoding in this store is known to be valid.
+    pub fn is_validated(&self) -> bool {
+        self.validated
+    }
+
+    /// Checks that every encoding in this store is valid. If they are, later accesses
+    /// will skip validation.
+    pub fn validate(&mut self) -> Choice {
+        let valid = self.reprs.iter().fold(Choice::from(1), |acc, repr| {
+            acc & G::from_bytes(repr).is_some()
+        });
+        self.validated = bool::from(valid);
+        valid
+    }
+
+    /// Decompresses the point at index `i`.
+    ///
+    /// This function will panic if `i` is out of bounds.
+    pub fn get(&self, i: usize) -> CtOption<G> {
+        self.decompress(&self.reprs[i])
+    }
+
+    /// Decompresses the points starting at index `start` into `out`, returning whether all
+    /// of them were valid. Entries of `out` corresponding to invalid encodings are left
+    /// unchanged.
+    ///
+    /// This function will panic if `start + out.len() > self.len()`.
+    pub fn get_batch(&self, start: usize, out: &mut [G]) -> Choice {
+        let reprs = &self.reprs[start..start + out.len()];
+
+        let mut valid = Choice::from(1);
+        for (repr, out) in reprs.iter().zip(out.iter_mut()) {
+            let p = self.decompress(repr);
+            valid &= p.is_some();
+            if let Some(p) = Option::from(p) {
+                *out = p;
+            }
+        }
+        valid
+    }
+
+    fn decompress(&self, repr: &G::Repr) -> CtOption<G> {
+        if self.validated {
+            G::from_bytes_unchecked(repr)
+        } else {
+            G::from_bytes(repr)
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! Access to the coordinates of elliptic curve points.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use ff::Field;
+use subtle::{Choice, ConditionallySelectable, CtOption};
+
+use crate::Curve;
+
+#[cfg(feature = "serde")]
+pub mod serde_with;
+
+/// Efficient representation of an elliptic curve point whose internal coordinates
+/// `(X, Y, Z)` can be accessed directly.
+///
+/// The coordinate system (for example Jacobian or homogeneous projective coordinates) is
+/// defined by the implementation. These methods expose the raw representation so that
+/// batch kernels can operate on it; they are not a stable encoding.
+pub trait CurveCoordinates: Curve {
+    /// The field over which the curve is defined.
+    type Base: Field;
+
+    /// Returns the internal coordinates `(X, Y, Z)` of this element.
+    fn coordinates(&self) -> (Self::Base, Self::Base, Self::Base);
+
+    /// Constructs an element from its internal coordinates, without checking that they
+    /// represent a valid element.
+    ///
+    /// **This is dangerous to call unless the coordinates were obtained from
+    /// [`CurveCoordinates::coordinates`] or are otherwise trusted; API invariants may be
+    /// broken.**
+    fn from_coordinates_unchecked(x: Self::Base, y: Self::Base, z: Self::Base) -> Self;
+}
+
+/// Efficient representation of an elliptic curve point in Jacobian coordinates.
+///
+/// The coordinates `(X, Y, Z)` with `Z != 0` represent the affine point
+/// `(X / Z^2, Y / Z^3)`, and coordinates with `Z = 0` represent the identity. The
+/// representation of a point is not unique: `(λ^2 X, λ^3 Y, λ Z)` represents the same
+/// point for every non-zero `λ`.
+pub trait JacobianCoordinates: CurveCoordinates {
+    /// Returns the Jacobian coordinates `(X, Y, Z)` of this element.
+    fn jacobian_coordinates(&self) -> (Self::Base, Self::Base, Self::Base) {
+        self.coordinates()
+    }
+
+    /// Attempts to construct an element from Jacobian coordinates, checking that they
+    /// represent a valid element.
+    fn new_jacobian(x: Self::Base, y: Self::Base, z: Self::Base) -> CtOption<Self>;
+
+    /// Constructs an element from Jacobian coordinates, without checking that they
+    /// represent a valid element.
+    ///
+    /// **This is dangerous to call unless you trust the coordinates; otherwise, API
+    /// invariants may be broken.** Please consider using
+    /// [`JacobianCoordinates::new_jacobian`] instead.
+    fn new_jacobian_unchecked(x: Self::Base, y: Self::Base, z: Self::Base) -> Self {
+        Self::from_coordinates_unchecked(x, y, z)
+    }
+
+    /// Attempts to construct a batch of elements from Jacobian coordinates, checking that
+    /// they all represent valid elements.
+    ///
+    /// Returns `None` if any of the coordinates are invalid, without revealing which. The
+    /// default implementation validates each element independently; implementations may
+    /// override it to share work between elements.
+    #[cfg(feature = "alloc")]
+    fn batch_new_jacobian(coords: &[(Self::Base, Self::Base, Self::Base)]) -> CtOption<Vec<Self>> {
+        let mut valid = Choice::from(1);
+        let points = coords
+            .iter()
+            .map(|&(x, y, z)| {
+                valid &= Self::new_jacobian(x, y, z).is_some();
+                Self::new_jacobian_unchecked(x, y, z)
+            })
+            .collect();
+
+        CtOption::new(points, valid)
+    }
+
+    /// Converts a batch of elements into affine elements, using Montgomery's trick to
+    /// perform a single field inversion for the whole batch. This function will panic if
+    /// `p.len() != q.len()`.
+    ///
+    /// The identity (`Z = 0`) is mapped to the affine identity. Implementations of
+    /// [`Curve::batch_normalize`] for curves in Jacobian coordinates can delegate to this.
+    #[cfg(feature = "alloc")]
+    fn batch_normalize_jacobian(p: &[Self], q: &mut [Self::AffineRepr])
+    where
+        Self::AffineRepr: AffineCoordinates<Base = Self::Base> + ConditionallySelectable,
+    {
+        assert_eq!(p.len(), q.len());
+
+        let coords = p
+            .iter()
+            .map(|p| p.jacobian_coordinates())
+            .collect::<Vec<_>>();
+        let mut z_invs = coords.iter().map(|&(_, _, z)| z).collect::<Vec<_>>();
+        crate::util::batch_invert(&mut z_invs);
+
+        let identity = Self::identity().to_affine();
+        for ((&(x, y, z), z_inv), q) in coords.iter().zip(z_invs).zip(q.iter_mut()) {
+            let z_inv2 = z_inv.square();
+            let point =
+                Self::AffineRepr::from_coordinates_unchecked(x * z_inv2, y * z_inv2 * z_inv);
+            *q = Self::AffineRepr::conditional_select(&point, &identity, z.is_zero());
+        }
+    }
+}
+
+/// Affine representation of an elliptic curve point whose coordinates `(x, y)` can be
+/// accessed directly.
+pub trait AffineCoordinates: Sized {
+    /// The field over which the curve is defined.
+    type Base: Field;
+
+    /// Returns the coordinates `(x, y)` of this element, or `None` if it is the
+    /// identity.
+    fn coordinates(&self) -> CtOption<(Self::Base, Self::Base)>;
+
+    /// Attempts to construct an element from its coordinates, checking that they
+    /// represent a valid element (on the curve, and in the correct subgroup).
+    fn from_coordinates(x: Self::Base, y: Self::Base) -> CtOption<Self>;
+
+    /// Constructs an element from its coordinates, without checking that they represent
+    /// a valid element.
+    ///
+    /// **This is dangerous to call unless you trust the coordinates; otherwise, API
+    /// invariants may be broken.** Please consider using
+    /// [`AffineCoordinates::from_coordinates`] instead.
+    fn from_coordinates_unchecked(x: Self::Base, y: Self::Base) -> Self;
+
+    /// Returns the "sign" of the y-coordinate of this element, which distinguishes `y`
+    /// from `-y` (and so, on short Weierstrass curves, this element from its negation).
+    ///
+    /// For curves over prime fields this is whether `y` is odd; for curves over extension
+    /// fields, it is whether `y` is lexicographically larger than `-y`. Returns `false`
+    /// for the identity.
+    ///
+    /// This is the bit that compressed encodings, ECDSA recovery identifiers and BIP 340
+    /// style tweaking depend on. Implementations should compute it without first
+    /// converting `y` to its canonical byte encoding where possible.
+    fn y_parity(&self) -> Choice;
+
+    /// Attempts to construct a batch of elements from their coordinates, checking that
+    /// they all represent valid elements.
+    ///
+    /// Returns `None` if any of the coordinates are invalid, without revealing which. The
+    /// default implementation validates each element independently; implementations may
+    /// override it to share work between elements.
+    #[cfg(feature = "alloc")]
+    fn batch_from_coordinates(coords: &[(Self::Base, Self::Base)]) -> CtOption<Vec<Self>> {
+        let mut valid = Choice::from(1);
+        let points = coords
+            .iter()
+            .map(|&(x, y)| {
+                valid &= Self::from_coordinates(x, y).is_some();
+                Self::from_coordinates_unchecked(x, y)
+            })
+            .collect();
+
+        CtOption::new(points, valid)
+    }
+}
+
+/// Affine representation of a point on a short Weierstrass curve `y^2 = x^3 + ax + b`.
+pub trait ShortWeierstrass: AffineCoordinates {
+    /// Returns the curve constant `a`.
+    fn a() -> Self::Base;
+
+    /// Returns the curve constant `b`.
+    fn b() -> Self::Base;
+
+    /// Attempts to recover an element from its x-coordinate and the parity of its
+    /// y-coordinate (as returned by [`AffineCoordinates::y_parity`]), checking that the
+    /// result is a valid element.
+    ///
+    /// Returns `None` if `x^3 + ax + b` is not a square, if the parity cannot be
+    /// satisfied (because `y = 0`), or if the recovered point is not in the correct
+    /// subgroup.
+    fn from_x(x: Self::Base, y_is_odd: Choice) -> CtOption<Self> {
+        let (p, valid) = from_x_inner(x, y_is_odd);
+        CtOption::new(p, valid)
+    }
+
+    /// Attempts to recover a batch of elements from their x-coordinates and the parities
+    /// of their y-coordinates, checking that they are all valid elements.
+    ///
+    /// Returns `None` if any of the elements cannot be recovered, without revealing
+    /// which. The default implementation recovers each element independently;
+    /// implementations whose base field supports it may override it to share work
+    /// between elements (for example by batching inversions and square roots).
+    #[cfg(feature = "alloc")]
+    fn batch_from_x(xs: &[(Self::Base, Choice)]) -> CtOption<Vec<Self>> {
+        let mut valid = Choice::from(1);
+        let points = xs
+            .iter()
+            .map(|&(x, y_is_odd)| {
+                let (p, p_valid) = from_x_inner(x, y_is_odd);
+                valid &= p_valid;
+                p
+            })
+            .collect();
+
+        CtOption::new(points, valid)
+    }
+}
+
+/// Recovers a point from its x-coordinate and y-parity, returning the (possibly invalid)
+/// point along with whether it is valid.
+fn from_x_inner<A: ShortWeierstrass>(x: A::Base, y_is_odd: Choice) -> (A, Choice) {
+    let y = (x.square() * x + A::a() * x + A::b()).sqrt();
+    let is_square = y.is_some();
+    let y = y.unwrap_or(A::Base::ZERO);
+
+    // Select the square root with the requested parity.
+    let flip = A::from_coordinates_unchecked(x, y).y_parity() ^ y_is_odd;
+    let y = A::Base::conditional_select(&y, &-y, flip);
+
+    let parity_ok = !(A::from_coordinates_unchecked(x, y).y_parity() ^ y_is_odd);
+    let valid = A::from_coordinates(x, y).is_some();
+
+    (
+        A::from_coordinates_unchecked(x, y),
+        is_square & parity_ok & valid,
+    )
+}
+
+/// A vector of elliptic curve points, stored as three separate contiguous arrays of
+/// `X`, `Y` and `Z` coordinates.
+///
+/// This "structure of arrays" layout lets batch kernels (normalization, validation,
+/// multiscalar multiplication preprocessing) stream over a single coordinate at a time.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct PointVec<C: CurveCoordinates> {
+    x: Vec<C::Base>,
+    y: Vec<C::Base>,
+    z: Vec<C::Base>,
+}
+
+#[cfg(feature = "alloc")]
+impl<C: CurveCoordinates> Default for PointVec<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: CurveCoordinates> PointVec<C> {
+    /// Constructs an empty vector without allocating.
+    pub fn new() -> Self {
+        PointVec {
+            x: vec![],
+            y: vec![],
+            z: vec![],
+        }
+    }
+
+    /// Constructs a vector containing the given points.
+    pub fn from_points(points: &[C]) -> Self {
+        let mut ret = PointVec {
+            x: Vec::with_capacity(points.len()),
+            y: Vec::with_capacity(points.len()),
+            z: Vec::with_capacity(points.len()),
+        };
+        for p in points {
+            ret.push(p);
+        }
+        ret
+    }
+
+    /// Returns the points in this vector.
+    pub fn to_points(&self) -> Vec<C> {
+        self.x
+            .iter()
+            .zip(self.y.iter())
+            .zip(self.z.iter())
+            .map(|((x, y), z)| C::from_coordinates_unchecked(*x, *y, *z))
+            .collect()
+    }
+
+    /// Returns the number of points in this vector.
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Returns `true` if this vector contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+
+    /// Appends a point to the end of this vector.
+    pub fn push(&mut self, point: &C) {
+        let (x, y, z) = point.coordinates();
+        self.x.push(x);
+        self.y.push(y);
+        self.z.push(z);
+    }
+
+    /// Returns the point at index `i`, or `None` if it is out of bounds.
+    pub fn get(&self, i: usize) -> Option<C> {
+        if i < self.len() {
+            Some(C::from_coordinates_unchecked(
+                self.x[i], self.y[i], self.z[i],
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `X` coordinates of the points in this vector.
+    pub fn x(&self) -> &[C::Base] {
+        &self.x
+    }
+
+    /// Returns the `Y` coordinates of the points in this vector.
+    pub fn y(&self) -> &[C::Base] {
+        &self.y
+    }
+
+    /// Returns the `Z` coordinates of the points in this vector.
+    pub fn z(&self) -> &[C::Base] {
+        &self.z
+    }
+}
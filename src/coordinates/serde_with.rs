@@ -0,0 +1,157 @@
+//! [Serde] support for exchanging points as explicit coordinates.
+//!
+//! The modules here are intended for use with `#[serde(with = "...")]` on fields holding
+//! points. Each coordinate is serialized as the [`PrimeField::to_repr`] encoding of the
+//! corresponding base field element, and deserialization rejects coordinates that are
+//! non-canonical or do not represent a valid point:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Witness {
+//!     #[serde(with = "group::coordinates::serde_with::affine")]
+//!     point: G1Affine,
+//! }
+//! ```
+//!
+//! [Serde]: https://serde.rs
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use ff::PrimeField;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeTuple, Serializer};
+
+/// A field element, serialized as its canonical byte encoding.
+pub(crate) struct Element<F: PrimeField>(pub(crate) F);
+
+impl<F: PrimeField> Serialize for Element<F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0.to_repr().as_ref())
+    }
+}
+
+impl<'de, F: PrimeField> Deserialize<'de> for Element<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ElementVisitor<F>(PhantomData<F>);
+
+        impl<F: PrimeField> ElementVisitor<F> {
+            fn parse<E: de::Error>(repr: F::Repr) -> Result<Element<F>, E> {
+                Option::from(F::from_repr(repr))
+                    .map(Element)
+                    .ok_or_else(|| E::custom("non-canonical field element encoding"))
+            }
+        }
+
+        impl<'de, F: PrimeField> Visitor<'de> for ElementVisitor<F> {
+            type Value = Element<F>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let len = F::Repr::default().as_ref().len();
+                write!(f, "a field element encoding of {} bytes", len)
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                let mut repr = F::Repr::default();
+                if v.len() != repr.as_ref().len() {
+                    return Err(E::invalid_length(v.len(), &self));
+                }
+                repr.as_mut().copy_from_slice(v);
+                Self::parse(repr)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut repr = F::Repr::default();
+                let len = repr.as_ref().len();
+                for (i, byte) in repr.as_mut().iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                if seq.next_element::<u8>()?.is_some() {
+                    return Err(de::Error::invalid_length(len + 1, &self));
+                }
+                Self::parse(repr)
+            }
+        }
+
+        deserializer.deserialize_bytes(ElementVisitor(PhantomData))
+    }
+}
+
+/// (De)serializes an [`AffineCoordinates`](super::AffineCoordinates) point as its
+/// coordinates `(x, y)`.
+///
+/// The identity has no affine coordinates, and cannot be serialized.
+pub mod affine {
+    use super::*;
+    use crate::coordinates::AffineCoordinates;
+
+    /// Serializes `point` as its coordinates `(x, y)`.
+    pub fn serialize<T, S>(point: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AffineCoordinates,
+        T::Base: PrimeField,
+        S: Serializer,
+    {
+        let (x, y) = Option::from(point.coordinates())
+            .ok_or_else(|| ser::Error::custom("the identity has no affine coordinates"))?;
+
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&Element(x))?;
+        tuple.serialize_element(&Element(y))?;
+        tuple.end()
+    }
+
+    /// Deserializes a point from its coordinates `(x, y)`, checking that they represent a
+    /// valid point.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: AffineCoordinates,
+        T::Base: PrimeField,
+        D: Deserializer<'de>,
+    {
+        let (x, y) = <(Element<T::Base>, Element<T::Base>)>::deserialize(deserializer)?;
+
+        Option::from(T::from_coordinates(x.0, y.0))
+            .ok_or_else(|| de::Error::custom("coordinates do not represent a valid point"))
+    }
+}
+
+/// (De)serializes a [`JacobianCoordinates`](super::JacobianCoordinates) point as its
+/// Jacobian coordinates `(X, Y, Z)`.
+pub mod jacobian {
+    use super::*;
+    use crate::coordinates::JacobianCoordinates;
+
+    /// Serializes `point` as its Jacobian coordinates `(X, Y, Z)`.
+    pub fn serialize<T, S>(point: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: JacobianCoordinates,
+        T::Base: PrimeField,
+        S: Serializer,
+    {
+        let (x, y, z) = point.jacobian_coordinates();
+
+        let mut tuple = serializer.serialize_tuple(3)?;
+        tuple.serialize_element(&Element(x))?;
+        tuple.serialize_element(&Element(y))?;
+        tuple.serialize_element(&Element(z))?;
+        tuple.end()
+    }
+
+    /// Deserializes a point from its Jacobian coordinates `(X, Y, Z)`, checking that they
+    /// represent a valid point.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: JacobianCoordinates,
+        T::Base: PrimeField,
+        D: Deserializer<'de>,
+    {
+        let (x, y, z) =
+            <(Element<T::Base>, Element<T::Base>, Element<T::Base>)>::deserialize(deserializer)?;
+
+        Option::from(T::new_jacobian(x.0, y.0, z.0))
+            .ok_or_else(|| de::Error::custom("coordinates do not represent a valid point"))
+    }
+}
@@ -0,0 +1,68 @@
+//! Human-readable formatting of group elements.
+//!
+//! Generic code cannot implement [`Display`](fmt::Display) or [`Debug`](fmt::Debug) for
+//! the concrete types it operates on, so this module provides wrappers that can be used in
+//! logs and error messages instead:
+//!
+//! ```ignore
+//! use group::display::{Hex, Normalized};
+//!
+//! // 0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac58...
+//! println!("{}", Hex(&point.to_affine()));
+//!
+//! // Affine { x: 0x17f1d3a7..., y: 0x08b3f481... }
+//! println!("{:?}", Normalized(&point));
+//! ```
+
+use core::fmt;
+
+use crate::{coordinates::AffineCoordinates, Curve, GroupEncoding};
+
+/// Formats an element as its `0x`-prefixed [`GroupEncoding`], in lowercase hex.
+///
+/// For curve points this is the compressed encoding. Both the [`Display`](fmt::Display)
+/// and [`Debug`](fmt::Debug) implementations produce the same output.
+pub struct Hex<'a, G: GroupEncoding>(pub &'a G);
+
+impl<'a, G: GroupEncoding> fmt::Display for Hex<'a, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("0x")?;
+        for byte in self.0.to_bytes().as_ref() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, G: GroupEncoding> fmt::Debug for Hex<'a, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Formats a curve point by its normalized affine coordinates.
+///
+/// The [`Debug`](fmt::Debug) output of an efficient representation usually shows its
+/// internal coordinates, which differ between equal points. This wrapper instead shows
+/// `Affine { x: .., y: .. }`, or `Identity` for the identity, so that equal points are
+/// always formatted identically.
+///
+/// Formatting normalizes the point, which requires a field inversion.
+pub struct Normalized<'a, C: Curve>(pub &'a C);
+
+impl<'a, C> fmt::Debug for Normalized<'a, C>
+where
+    C: Curve,
+    C::AffineRepr: AffineCoordinates,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Option::from(self.0.to_affine().coordinates()) {
+            Some((x, y)) => f
+                .debug_struct("Affine")
+                .field("x", &x)
+                .field("y", &y)
+                .finish(),
+            None => f.write_str("Identity"),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Support for sampling group elements with [`rand`].
+//!
+//! The orphan rules prevent this crate from implementing [`Distribution`] for
+//! [`Standard`] over every group. Curve crates can instead invoke
+//! [`impl_standard_distribution!`](crate::impl_standard_distribution) for their concrete
+//! types, after which their points can be sampled with `rng.gen()`:
+//!
+//! ```ignore
+//! group::impl_standard_distribution!(G1Projective, G1Affine);
+//!
+//! let p: G1Projective = rng.gen();
+//! let q: G1Affine = rng.gen();
+//! ```
+
+pub use rand::distributions::{Distribution, Standard};
+pub use rand::Rng;
+
+use crate::GroupCore;
+
+/// The uniform distribution over the non-identity elements of a group, as sampled by
+/// [`GroupCore::random`].
+///
+/// Unlike [`Standard`], this distribution can be used with any group:
+///
+/// ```ignore
+/// use group::distributions::{RandomNonIdentity, Rng};
+///
+/// let p: G = rng.sample(RandomNonIdentity);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomNonIdentity;
+
+impl<G: GroupCore> Distribution<G> for RandomNonIdentity {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> G {
+        G::random(rng)
+    }
+}
+
+/// Implements [`Distribution`] for [`Standard`] over a concrete group type, sampling with
+/// [`GroupCore::random`].
+///
+/// If an affine type is also given, points in affine form are sampled by converting a
+/// random point with [`Curve::to_affine`](crate::Curve::to_affine).
+#[macro_export]
+macro_rules! impl_standard_distribution {
+    ($group:ty) => {
+        impl $crate::distributions::Distribution<$group> for $crate::distributions::Standard {
+            fn sample<R: $crate::distributions::Rng + ?Sized>(&self, rng: &mut R) -> $group {
+                <$group as $crate::GroupCore>::random(rng)
+            }
+        }
+    };
+    ($curve:ty, $affine:ty) => {
+        $crate::impl_standard_distribution!($curve);
+
+        impl $crate::distributions::Distribution<$affine> for $crate::distributions::Standard {
+            fn sample<R: $crate::distributions::Rng + ?Sized>(&self, rng: &mut R) -> $affine {
+                $crate::Curve::to_affine(&<$curve as $crate::GroupCore>::random(rng))
+            }
+        }
+    };
+}
@@ -0,0 +1,37 @@
+//! Encodings of curve points that are indistinguishable from uniformly random bytes.
+//!
+//! [Elligator 2] maps field elements to curve points with an efficiently computable
+//! inverse over roughly half of the curve. Protocols that must hide public keys in
+//! random-looking traffic (such as censorship-resistant transports) sample keys until
+//! they find one in that half, and send its *representative* instead of its encoding.
+//!
+//! [Elligator 2]: https://elligator.cr.yp.to/elligator-20130828.pdf
+
+use rand_core::RngCore;
+use subtle::CtOption;
+
+/// A group whose elements can be encoded as uniformly random byte strings, through an
+/// invertible map-to-curve such as Elligator 2.
+pub trait Elligator: Sized {
+    /// The encoding of a representative.
+    ///
+    /// Every byte string of this type must decode to an element with
+    /// [`Elligator::from_representative`].
+    type Representative: Copy + Default + Send + Sync + 'static + AsRef<[u8]> + AsMut<[u8]>;
+
+    /// Maps a representative to the element it encodes.
+    ///
+    /// This is the forward map-to-curve, and accepts every byte string. Bits of the
+    /// encoding that the map does not use (such as the high bits of a field element) are
+    /// ignored.
+    fn from_representative(bytes: &Self::Representative) -> Self;
+
+    /// Returns a representative of this element, or `None` if the element is not in the
+    /// image of the map.
+    ///
+    /// Bits of the encoding that the map does not use, and any other choices between
+    /// equivalent representatives, are filled in from `rng`, so that representatives of
+    /// uniformly random elements in the image are uniformly random byte strings. This
+    /// runs in constant time with respect to the element.
+    fn to_representative(&self, rng: impl RngCore) -> CtOption<Self::Representative>;
+}
@@ -0,0 +1,117 @@
+//! A self-describing envelope for long-lived point encodings.
+//!
+//! A bare [`GroupEncoding`] does not record which curve it belongs to, and encodings for
+//! different curves often have the same length; decoding a key file or structured
+//! reference string with the wrong curve can then produce a valid-looking but unrelated
+//! point. The envelope defined here prefixes the encoding with a header:
+//!
+//! | Bytes   | Contents                                      |
+//! |---------|-----------------------------------------------|
+//! | `0..4`  | [`TaggedEncoding::CURVE_ID`]                  |
+//! | `4`     | Envelope format version ([`VERSION`])         |
+//! | `5`     | Flags (reserved, must be zero)                |
+//! | `6..`   | The [`GroupEncoding`] of the point            |
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::GroupEncoding;
+
+/// The current version of the envelope format.
+pub const VERSION: u8 = 1;
+
+/// The length of the envelope header.
+pub const HEADER_LEN: usize = 6;
+
+/// Group elements that can be encoded inside a self-describing envelope.
+pub trait TaggedEncoding: GroupEncoding {
+    /// A four-byte identifier for this group, distinct from that of every other group
+    /// that may be stored in the same kind of artifact.
+    const CURVE_ID: [u8; 4];
+
+    /// Encodes this element inside an envelope.
+    #[cfg(feature = "alloc")]
+    fn to_tagged_bytes(&self) -> Vec<u8> {
+        let repr = self.to_bytes();
+        let mut bytes = Vec::with_capacity(HEADER_LEN + repr.as_ref().len());
+        bytes.extend_from_slice(&Self::CURVE_ID);
+        bytes.push(VERSION);
+        bytes.push(0);
+        bytes.extend_from_slice(repr.as_ref());
+        bytes
+    }
+
+    /// Decodes an element from an envelope, checking that the header matches this group
+    /// and that the payload is a valid encoding.
+    ///
+    /// This is not constant time, and is intended for public artifacts.
+    fn from_tagged_bytes(bytes: &[u8]) -> Result<Self, EnvelopeError> {
+        let mut repr = Self::Repr::default();
+        let expected_len = HEADER_LEN + repr.as_ref().len();
+        if bytes.len() != expected_len {
+            return Err(EnvelopeError::InvalidLength {
+                expected: expected_len,
+                found: bytes.len(),
+            });
+        }
+
+        let (header, payload) = bytes.split_at(HEADER_LEN);
+        let mut curve_id = [0; 4];
+        curve_id.copy_from_slice(&header[..4]);
+        if curve_id != Self::CURVE_ID {
+            return Err(EnvelopeError::CurveMismatch {
+                expected: Self::CURVE_ID,
+                found: curve_id,
+            });
+        }
+        if header[4] != VERSION {
+            return Err(EnvelopeError::UnsupportedVersion(header[4]));
+        }
+        if header[5] != 0 {
+            return Err(EnvelopeError::UnsupportedFlags(header[5]));
+        }
+
+        repr.as_mut().copy_from_slice(payload);
+        Option::from(Self::from_bytes(&repr)).ok_or(EnvelopeError::InvalidPoint)
+    }
+}
+
+/// The reasons an envelope can fail to decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// The envelope does not have the length of an envelope for this group.
+    InvalidLength { expected: usize, found: usize },
+    /// The envelope holds an element of a different group.
+    CurveMismatch { expected: [u8; 4], found: [u8; 4] },
+    /// The envelope was produced by an unsupported version of the format.
+    UnsupportedVersion(u8),
+    /// The envelope has flags set that this version of the format does not define.
+    UnsupportedFlags(u8),
+    /// The payload is not a valid encoding of a group element.
+    InvalidPoint,
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvelopeError::InvalidLength { expected, found } => write!(
+                f,
+                "expected an envelope of {} bytes, found {} bytes",
+                expected, found
+            ),
+            EnvelopeError::CurveMismatch { expected, found } => write!(
+                f,
+                "expected an element with curve id {:02x?}, found curve id {:02x?}",
+                expected, found
+            ),
+            EnvelopeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported envelope version {}", version)
+            }
+            EnvelopeError::UnsupportedFlags(flags) => {
+                write!(f, "unsupported envelope flags {:#04x}", flags)
+            }
+            EnvelopeError::InvalidPoint => f.write_str("the payload is not a valid element"),
+        }
+    }
+}
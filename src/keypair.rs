@@ -0,0 +1,148 @@
+//! A key pair consisting of a secret scalar and the corresponding public point.
+
+use core::fmt;
+
+use ff::{Field, FromUniformBytes, PrimeField};
+use rand_core::{CryptoRng, RngCore};
+use subtle::{ConstantTimeEq, CtOption};
+
+#[cfg(feature = "serde")]
+use crate::coordinates::serde_with::Element;
+use crate::prime::PrimeCurve;
+
+/// A secret scalar `x` together with its public point `[x]G`, where `G` is the fixed
+/// generator of the curve.
+///
+/// Every constructor rejects the zero scalar (whose public point is the identity), and
+/// the public point is always derived from the secret scalar rather than supplied
+/// separately, so a `Keypair` is always consistent. The secret scalar is not included in
+/// the `Debug` output. With the `zeroize` feature flag, it is also overwritten with zero
+/// when the key pair is dropped.
+///
+/// With the `serde` feature flag, a key pair is serialized as the encoding of its secret
+/// scalar, as returned by [`Keypair::to_bytes`].
+pub struct Keypair<C: PrimeCurve> {
+    secret: Secret<C::Scalar>,
+    public: C::Affine,
+}
+
+/// The secret scalar of a [`Keypair`], wrapped so that it can be zeroized.
+#[derive(Clone, Copy)]
+struct Secret<F: Field>(F);
+
+impl<F: Field> Default for Secret<F> {
+    fn default() -> Self {
+        Secret(F::ZERO)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<F: Field> zeroize::DefaultIsZeroes for Secret<F> {}
+
+impl<C: PrimeCurve> Keypair<C> {
+    /// Generates a key pair with a secret scalar chosen uniformly at random from the
+    /// non-zero scalars.
+    pub fn random(mut rng: impl CryptoRng + RngCore) -> Self {
+        loop {
+            let secret = C::Scalar::random(&mut rng);
+            if let Some(keypair) = Option::from(Self::from_secret(secret)) {
+                return keypair;
+            }
+        }
+    }
+
+    /// Constructs the key pair for the given secret scalar, or `None` if it is zero.
+    pub fn from_secret(secret: C::Scalar) -> CtOption<Self> {
+        let public = (C::generator() * secret).to_affine();
+        CtOption::new(
+            Keypair {
+                secret: Secret(secret),
+                public,
+            },
+            !secret.is_zero(),
+        )
+    }
+
+    /// Derives a key pair from uniformly random seed bytes, by reducing them modulo the
+    /// order of the scalar field. Returns `None` if the reduction is zero.
+    ///
+    /// `N` should be large enough that the reduction is close to uniform; 64 bytes is
+    /// typical for 256-bit scalar fields.
+    pub fn from_seed<const N: usize>(seed: &[u8; N]) -> CtOption<Self>
+    where
+        C::Scalar: FromUniformBytes<N>,
+    {
+        Self::from_secret(C::Scalar::from_uniform_bytes(seed))
+    }
+
+    /// Parses a key pair from the encoding of its secret scalar, returning `None` if the
+    /// encoding is not canonical or is the encoding of zero.
+    pub fn from_bytes(bytes: &<C::Scalar as PrimeField>::Repr) -> CtOption<Self> {
+        C::Scalar::from_repr(*bytes).and_then(Self::from_secret)
+    }
+
+    /// Returns the encoding of the secret scalar, from which the key pair can be
+    /// recovered with [`Keypair::from_bytes`].
+    pub fn to_bytes(&self) -> <C::Scalar as PrimeField>::Repr {
+        self.secret.0.to_repr()
+    }
+
+    /// Returns the secret scalar.
+    pub fn secret(&self) -> &C::Scalar {
+        &self.secret.0
+    }
+
+    /// Returns the public point.
+    pub fn public(&self) -> &C::Affine {
+        &self.public
+    }
+}
+
+impl<C: PrimeCurve> Clone for Keypair<C> {
+    fn clone(&self) -> Self {
+        Keypair {
+            secret: self.secret,
+            public: self.public,
+        }
+    }
+}
+
+impl<C: PrimeCurve> fmt::Debug for Keypair<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair")
+            .field("public", &self.public)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: PrimeCurve> ConstantTimeEq for Keypair<C> {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.secret.0.ct_eq(&other.secret.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<C: PrimeCurve> Drop for Keypair<C> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.secret);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<C: PrimeCurve> zeroize::ZeroizeOnDrop for Keypair<C> {}
+
+#[cfg(feature = "serde")]
+impl<C: PrimeCurve> serde::Serialize for Keypair<C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&Element(self.secret.0), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: PrimeCurve> serde::Deserialize<'de> for Keypair<C> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Element(secret) = serde::Deserialize::deserialize(deserializer)?;
+        Option::from(Self::from_secret(secret))
+            .ok_or_else(|| serde::de::Error::custom("the secret scalar is zero"))
+    }
+}
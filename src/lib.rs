@@ -9,22 +9,68 @@ extern crate alloc;
 // Re-export ff to make version-matching easier.
 pub use ff;
 
+/// Increments one of the `trace` module's counters by `$n` (or one) when the `trace` feature is
+/// enabled, and does nothing otherwise.
+macro_rules! trace {
+    ($counter:ident) => {
+        trace!($counter, 1)
+    };
+    ($counter:ident, $n:expr) => {
+        #[cfg(feature = "trace")]
+        crate::trace::$counter.fetch_add($n, core::sync::atomic::Ordering::Relaxed);
+    };
+}
+
 use core::fmt;
 use core::iter::Sum;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use ff::PrimeField;
-use rand_core::RngCore;
-use subtle::{Choice, CtOption};
+use rand_core::{CryptoRng, RngCore};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
+pub mod accumulator;
+pub mod addition_chain;
+pub mod chain;
 pub mod cofactor;
+pub mod commitment;
+#[cfg(feature = "alloc")]
+pub mod compressed;
+pub mod coordinates;
+#[cfg(feature = "display")]
+pub mod display;
+#[cfg(feature = "rand")]
+pub mod distributions;
+pub mod elligator;
+pub mod envelope;
+pub mod keypair;
+mod macros;
+#[cfg(feature = "alloc")]
+pub mod msm_job;
+pub mod mul;
+pub mod non_identity;
 pub mod prime;
-#[cfg(feature = "tests")]
+pub mod psi;
+mod send_sync;
+#[cfg(all(feature = "alloc", feature = "proptest"))]
+pub mod strategies;
+pub mod table;
+#[cfg(feature = "alloc")]
+pub mod terms;
+#[cfg(feature = "tests-core")]
 pub mod tests;
+#[cfg(feature = "trace")]
+pub mod trace;
+pub mod transcript;
+pub mod util;
+pub mod validation;
+pub mod vartime;
 
 #[cfg(feature = "alloc")]
 mod wnaf;
 #[cfg(feature = "alloc")]
-pub use self::wnaf::{Wnaf, WnafBase, WnafGroup, WnafScalar};
+pub use self::wnaf::{
+    wnaf_window_from_costs, AffineWnafBase, Wnaf, WnafBase, WnafGroup, WnafScalar,
+};
 
 /// A helper trait for types with a group operation.
 pub trait GroupOps<Rhs = Self, Output = Self>:
@@ -38,6 +84,18 @@ impl<T, Rhs, Output> GroupOps<Rhs, Output> for T where
 }
 
 /// A helper trait for references with a group operation.
+///
+/// This lets generic code operate on borrowed right-hand sides without copying them:
+///
+/// ```
+/// use group::Group;
+///
+/// fn sum_and_difference<G: Group>(a: G, b: &G) -> (G, G) {
+///     let mut diff = a;
+///     diff -= b;
+///     (a + b, diff)
+/// }
+/// ```
 pub trait GroupOpsOwned<Rhs = Self, Output = Self>: for<'r> GroupOps<&'r Rhs, Output> {}
 impl<T, Rhs, Output> GroupOpsOwned<Rhs, Output> for T where T: for<'r> GroupOps<&'r Rhs, Output> {}
 
@@ -48,13 +106,58 @@ impl<T, Rhs, Output> ScalarMul<Rhs, Output> for T where T: Mul<Rhs, Output = Out
 {}
 
 /// A helper trait for references implementing group scalar multiplication.
+///
+/// ```
+/// use group::Group;
+///
+/// fn scale_all<G: Group>(points: &mut [G], scalar: &G::Scalar) {
+///     for p in points.iter_mut() {
+///         *p *= scalar;
+///     }
+/// }
+/// ```
 pub trait ScalarMulOwned<Rhs, Output = Self>: for<'r> ScalarMul<&'r Rhs, Output> {}
 impl<T, Rhs, Output> ScalarMulOwned<Rhs, Output> for T where T: for<'r> ScalarMul<&'r Rhs, Output> {}
 
+/// A helper trait for affine representations implementing scalar multiplication, with the
+/// result in the corresponding efficient representation.
+pub trait AffineScalarMul<Rhs, Output>:
+    Mul<Rhs, Output = Output> + for<'r> Mul<&'r Rhs, Output = Output>
+{
+}
+
+impl<T, Rhs, Output> AffineScalarMul<Rhs, Output> for T where
+    T: Mul<Rhs, Output = Output> + for<'r> Mul<&'r Rhs, Output = Output>
+{
+}
+
+/// A helper trait for affine representations with a group operation, with the result in
+/// the corresponding efficient representation.
+pub trait AffineGroupOps<Rhs, Output>:
+    Add<Rhs, Output = Output> + Sub<Rhs, Output = Output>
+{
+}
+
+impl<T, Rhs, Output> AffineGroupOps<Rhs, Output> for T where
+    T: Add<Rhs, Output = Output> + Sub<Rhs, Output = Output>
+{
+}
+
+/// A helper trait for affine representations with a group operation on references, with
+/// the result in the corresponding efficient representation.
+pub trait AffineGroupOpsOwned<Rhs, Output>: for<'r> AffineGroupOps<&'r Rhs, Output> {}
+impl<T, Rhs, Output> AffineGroupOpsOwned<Rhs, Output> for T where
+    T: for<'r> AffineGroupOps<&'r Rhs, Output>
+{
+}
+
 /// This trait represents an element of a cryptographic group.
-pub trait Group:
+///
+/// Unlike [`Group`], this trait does not require elements to be `Copy`, so it can be
+/// implemented for groups whose elements are large (such as pairing target groups) or
+/// heap-allocated.
+pub trait GroupCore:
     Clone
-    + Copy
     + fmt::Debug
     + Eq
     + Sized
@@ -66,8 +169,8 @@ pub trait Group:
     + Neg<Output = Self>
     + GroupOps
     + GroupOpsOwned
-    + ScalarMul<<Self as Group>::Scalar>
-    + ScalarMulOwned<<Self as Group>::Scalar>
+    + ScalarMul<<Self as GroupCore>::Scalar>
+    + ScalarMulOwned<<Self as GroupCore>::Scalar>
 {
     /// Scalars modulo the order of this group's scalar field.
     type Scalar: PrimeField;
@@ -75,9 +178,47 @@ pub trait Group:
     /// Returns an element chosen uniformly at random from the non-identity elements of
     /// this group.
     ///
+    /// For prime-order groups, [`PrimeGroup::random_including_identity`] samples
+    /// uniformly from the whole group instead.
+    ///
     /// This function is non-deterministic, and samples from the user-provided RNG.
+    ///
+    /// [`PrimeGroup::random_including_identity`]: prime::PrimeGroup::random_including_identity
     fn random(rng: impl RngCore) -> Self;
 
+    /// Returns an element chosen uniformly at random from the non-identity elements of
+    /// this group, using a cryptographically secure RNG.
+    ///
+    /// This behaves identically to [`GroupCore::random`], but allows security-sensitive
+    /// call sites to require at the type level that the RNG implements [`CryptoRng`].
+    fn random_secure(rng: impl CryptoRng + RngCore) -> Self {
+        Self::random(rng)
+    }
+
+    /// Returns `n` elements chosen independently and uniformly at random from the
+    /// non-identity elements of this group.
+    ///
+    /// The default implementation calls [`GroupCore::random`] `n` times. Implementations
+    /// may override it to amortize the cost of sampling across the batch (for example by
+    /// normalizing intermediate points together).
+    ///
+    /// This function is non-deterministic, and samples from the user-provided RNG.
+    #[cfg(feature = "alloc")]
+    fn random_batch(mut rng: impl RngCore, n: usize) -> alloc::vec::Vec<Self> {
+        (0..n).map(|_| Self::random(&mut rng)).collect()
+    }
+
+    /// Returns `N` elements chosen independently and uniformly at random from the
+    /// non-identity elements of this group.
+    ///
+    /// This is the fixed-size equivalent of `GroupCore::random_batch`, and does not
+    /// require `alloc`.
+    ///
+    /// This function is non-deterministic, and samples from the user-provided RNG.
+    fn random_array<const N: usize>(mut rng: impl RngCore) -> [Self; N] {
+        [(); N].map(|_| Self::random(&mut rng))
+    }
+
     /// Returns the additive identity, also known as the "neutral element".
     fn identity() -> Self;
 
@@ -88,19 +229,119 @@ pub trait Group:
     fn is_identity(&self) -> Choice;
 
     /// Doubles this element.
+    ///
+    /// The default implementation adds this element to itself. Implementations should
+    /// override it when a specialized doubling formula is available.
     #[must_use]
-    fn double(&self) -> Self;
+    fn double(&self) -> Self {
+        self.clone() + self
+    }
+}
+
+/// This trait represents an element of a cryptographic group whose elements are `Copy`.
+///
+/// It is implemented for every type that implements [`GroupCore`] and `Copy`.
+///
+/// # Examples
+///
+/// A Pedersen commitment to `value` with blinding factor `blind`, and the check of a
+/// Schnorr signature `(R, s)` on challenge `c` for public key `pk`, written once for
+/// every group:
+///
+/// ```
+/// use group::{ff::Field, Group, GroupEncoding};
+/// use rand_core::RngCore;
+///
+/// fn commit<G: Group>(h: &G, value: G::Scalar, mut rng: impl RngCore) -> (G, G::Scalar) {
+///     let blind = G::Scalar::random(&mut rng);
+///     (G::generator() * value + *h * blind, blind)
+/// }
+///
+/// fn schnorr_verify<G: Group + GroupEncoding>(
+///     pk: &G,
+///     r: &G::Repr,
+///     s: &G::Scalar,
+///     c: &G::Scalar,
+/// ) -> bool {
+///     match Option::<G>::from(G::from_bytes(r)) {
+///         // [s] G == R + [c] PK
+///         Some(r) => G::generator() * s == r + *pk * c,
+///         None => false,
+///     }
+/// }
+/// ```
+pub trait Group: GroupCore + Copy {}
+
+impl<T: GroupCore + Copy> Group for T {}
+
+/// A group whose `Default` value is the identity.
+///
+/// [`GroupCore`] does not require `Default`, and implementations that provide it are not
+/// otherwise required to return the identity. Generic code that zero-initializes
+/// buffers or accumulators with `Default::default()` should require this trait, rather
+/// than assuming the convention holds.
+///
+/// Implementing this trait is a promise that `Self::default() == Self::identity()`,
+/// which the `default_is_identity_tests` suite checks.
+pub trait DefaultIsIdentity: GroupCore + Default {}
+
+/// A group whose identity can be written as a constant.
+///
+/// This allows the identity to be used in `const` contexts, such as initializers of
+/// statics and arrays of precomputed points.
+pub trait ConstIdentity: GroupCore {
+    /// The identity of the group. This must be equal to [`GroupCore::identity`].
+    const IDENTITY: Self;
 }
 
 /// Efficient representation of an elliptic curve point guaranteed.
+///
+/// Points that may be derived from secret data should be compared with
+/// [`ConstantTimeEq::ct_eq`] rather than `==`. Implementations of `ct_eq` for projective
+/// representations are expected to compare cross-multiplied coordinates in constant
+/// time, without normalizing either point.
 pub trait Curve:
-    Group + GroupOps<<Self as Curve>::AffineRepr> + GroupOpsOwned<<Self as Curve>::AffineRepr>
+    Group
+    + ConstantTimeEq
+    + GroupOps<<Self as Curve>::AffineRepr>
+    + GroupOpsOwned<<Self as Curve>::AffineRepr>
 {
     /// The affine representation for this elliptic curve.
     type AffineRepr;
 
+    /// The estimated cost of adding two elements, in base-field multiplications.
+    ///
+    /// Together with [`Curve::MIXED_ADD_COST`] and [`Curve::DOUBLE_COST`], this lets
+    /// generic algorithms (such as `wnaf_window_from_costs`) and downstream schedulers
+    /// choose between strategies per curve. Squarings and multiplications by constants
+    /// count as multiplications. The defaults are the costs of the complete projective
+    /// formulas of [Renes–Costello–Batina] for curves with `a = 0`.
+    ///
+    /// [Renes–Costello–Batina]: https://eprint.iacr.org/2015/1060
+    const ADD_COST: usize = 14;
+
+    /// The estimated cost of adding an element in the affine representation to an
+    /// element, in base-field multiplications.
+    const MIXED_ADD_COST: usize = 13;
+
+    /// The estimated cost of doubling an element, in base-field multiplications.
+    const DOUBLE_COST: usize = 9;
+
     /// Converts a batch of projective elements into affine elements. This function will
     /// panic if `p.len() != q.len()`.
+    ///
+    /// The default implementation converts each element independently, performing one
+    /// field inversion per element. It cannot use Montgomery's trick itself, because
+    /// `Curve` gives no access to the coordinates of an element or to the field they live
+    /// in. Implementations should override it to share a single field inversion between
+    /// the elements; curves in Jacobian coordinates can delegate to
+    /// `JacobianCoordinates::batch_normalize_jacobian`:
+    ///
+    /// ```ignore
+    /// fn batch_normalize(p: &[Self], q: &mut [Self::AffineRepr]) {
+    ///     Self::batch_normalize_jacobian(p, q)
+    /// }
+    /// ```
     fn batch_normalize(p: &[Self], q: &mut [Self::AffineRepr]) {
         assert_eq!(p.len(), q.len());
 
@@ -109,10 +350,97 @@ pub trait Curve:
         }
     }
 
+    /// Converts a batch of projective elements into a vector of affine elements.
+    ///
+    /// See [`Curve::batch_normalize`] for details. The default implementation fills the
+    /// output with copies of the identity before normalizing into it; implementations
+    /// may override it to write the affine elements directly.
+    #[cfg(feature = "alloc")]
+    fn batch_normalize_vec(p: &[Self]) -> alloc::vec::Vec<Self::AffineRepr>
+    where
+        Self::AffineRepr: Clone,
+    {
+        let mut q = vec![Self::identity().to_affine(); p.len()];
+        Self::batch_normalize(p, &mut q);
+        q
+    }
+
+    /// Converts a batch of affine elements into their efficient representation. This
+    /// function will panic if `p.len() != q.len()`.
+    ///
+    /// This is the inverse of [`Curve::batch_normalize`].
+    fn batch_to_curve(p: &[Self::AffineRepr], q: &mut [Self]) {
+        assert_eq!(p.len(), q.len());
+
+        for (p, q) in p.iter().zip(q.iter_mut()) {
+            *q = Self::identity() + p;
+        }
+    }
+
+    /// Converts a batch of affine elements into a vector of elements in their efficient
+    /// representation.
+    ///
+    /// See [`Curve::batch_to_curve`] for details.
+    #[cfg(feature = "alloc")]
+    fn batch_to_curve_vec(p: &[Self::AffineRepr]) -> alloc::vec::Vec<Self> {
+        let mut q = vec![Self::identity(); p.len()];
+        Self::batch_to_curve(p, &mut q);
+        q
+    }
+
     /// Converts this element into its affine representation.
     fn to_affine(&self) -> Self::AffineRepr;
+
+    /// Sums a sequence of affine elements, using mixed addition.
+    ///
+    /// This avoids converting each element to its efficient representation before
+    /// adding it, as `points.map(|p| p.to_curve()).sum()` would.
+    fn sum_affine<'a, I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Self::AffineRepr>,
+        Self::AffineRepr: 'a,
+    {
+        points.into_iter().fold(Self::identity(), |acc, p| acc + p)
+    }
+
+    /// Determines if this element is equal to `other`, in variable time.
+    ///
+    /// This is intended for comparing public points, such as in verifiers. The default
+    /// implementation defers to `==`; implementations may override it to short-circuit,
+    /// for example on the `Z` coordinates or on identity flags.
+    fn eq_vartime(&self, other: &Self) -> bool {
+        self == other
+    }
 }
 
+/// Encoding of group elements as fixed-size byte strings.
+///
+/// # Validation levels
+///
+/// The deserialization methods of this trait and of [`UncompressedEncoding`] guarantee
+/// the following properties of the elements they return:
+///
+/// | Method | Well-formed | On the curve | In the correct subgroup |
+/// |--------|:-----------:|:------------:|:-----------------------:|
+/// | [`from_bytes`], [`from_uncompressed`] | ✓ | ✓ | ✓ |
+/// | [`from_bytes_on_curve_only`], [`from_uncompressed_on_curve_only`] | ✓ | ✓ | |
+/// | [`from_uncompressed_unchecked`] | ✓ | ✓ | |
+/// | [`from_bytes_unchecked`] | ✓ | | |
+///
+/// Implementations may perform more checks than the level guarantees (for example, an
+/// implementation of `from_bytes_unchecked` may reject points that are not on the curve),
+/// but callers must not rely on them. In particular, the default implementation of
+/// [`from_bytes_on_curve_only`] performs the full check of [`from_bytes`], and so saves
+/// no work unless the implementation overrides it. The default implementation of
+/// [`from_uncompressed_on_curve_only`] calls [`from_uncompressed_unchecked`], which
+/// already skips only the subgroup check.
+///
+/// [`from_bytes`]: GroupEncoding::from_bytes
+/// [`from_bytes_on_curve_only`]: GroupEncoding::from_bytes_on_curve_only
+/// [`from_bytes_unchecked`]: GroupEncoding::from_bytes_unchecked
+/// [`from_uncompressed`]: UncompressedEncoding::from_uncompressed
+/// [`from_uncompressed_on_curve_only`]: UncompressedEncoding::from_uncompressed_on_curve_only
+/// [`from_uncompressed_unchecked`]: UncompressedEncoding::from_uncompressed_unchecked
 pub trait GroupEncoding: Sized {
     /// The encoding of group elements.
     ///
@@ -133,9 +461,27 @@ pub trait GroupEncoding: Sized {
     /// encoding.as_mut().copy_from_slice(buf);
     /// ```
     ///
+    /// The above panics if `buf` has the wrong length; use
+    /// [`GroupEncoding::repr_from_slice`] for untrusted input.
+    ///
     /// It is recommended that the default should be the all-zeroes encoding.
     type Repr: Copy + Default + Send + Sync + 'static + AsRef<[u8]> + AsMut<[u8]>;
 
+    /// Copies `bytes` into an encoding, or returns `None` if `bytes` does not have the
+    /// length of an encoding.
+    ///
+    /// This does not check that the encoding is valid. Unlike copying into
+    /// `Self::Repr::default()` with `copy_from_slice`, it does not panic on input of the
+    /// wrong length, and so is suitable for parsing untrusted input.
+    fn repr_from_slice(bytes: &[u8]) -> Option<Self::Repr> {
+        let mut repr = Self::Repr::default();
+        if bytes.len() != repr.as_ref().len() {
+            return None;
+        }
+        repr.as_mut().copy_from_slice(bytes);
+        Some(repr)
+    }
+
     /// Attempts to deserialize a group element from its encoding.
     fn from_bytes(bytes: &Self::Repr) -> CtOption<Self>;
 
@@ -146,6 +492,20 @@ pub trait GroupEncoding: Sized {
     /// [`GroupEncoding::from_bytes`] instead.
     fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self>;
 
+    /// Attempts to deserialize a group element, checking that it is on the curve but not
+    /// that it is in the correct subgroup.
+    ///
+    /// **This is dangerous to call unless you check the subgroup membership of the
+    /// element yourself; otherwise, API invariants may be broken.** Please consider using
+    /// [`GroupEncoding::from_bytes`] instead.
+    ///
+    /// The default implementation calls [`GroupEncoding::from_bytes`], which also checks
+    /// subgroup membership, so callers only save work if the implementation overrides it
+    /// to skip that check.
+    fn from_bytes_on_curve_only(bytes: &Self::Repr) -> CtOption<Self> {
+        Self::from_bytes(bytes)
+    }
+
     /// Converts this element into its byte encoding. This may or may not support
     /// encoding the identity.
     // TODO: Figure out how to handle identity encoding generically.
@@ -154,21 +514,144 @@ pub trait GroupEncoding: Sized {
 
 /// Affine representation of a point on an elliptic curve that has a defined uncompressed
 /// encoding.
+///
+/// # Identity
+///
+/// The identity must have an encoding, distinct from the encoding of every other point,
+/// that is produced by [`UncompressedEncoding::to_uncompressed`] and accepted by
+/// [`UncompressedEncoding::from_uncompressed`]. Curves without a natural affine
+/// representation of the identity conventionally set an "infinity" flag in the
+/// otherwise-unused high bits of the encoding, and zero the coordinates (as is done by
+/// the [Zcash serialization] of BLS12-381 points).
+///
+/// [Zcash serialization]: https://github.com/zcash/librustzcash/blob/6e0364cd42a2b3d2b958a54771ef51a8db79dd29/pairing/src/bls12_381/README.md#serialization
 pub trait UncompressedEncoding: Sized {
     type Uncompressed: Default + AsRef<[u8]> + AsMut<[u8]>;
 
+    /// The length in bytes of [`UncompressedEncoding::Uncompressed`].
+    const UNCOMPRESSED_SIZE: usize;
+
+    /// Copies `bytes` into an uncompressed encoding, or returns `None` if `bytes` does not
+    /// have the length of an uncompressed encoding.
+    ///
+    /// See [`GroupEncoding::repr_from_slice`] for details.
+    fn uncompressed_from_slice(bytes: &[u8]) -> Option<Self::Uncompressed> {
+        let mut repr = Self::Uncompressed::default();
+        if bytes.len() != repr.as_ref().len() {
+            return None;
+        }
+        repr.as_mut().copy_from_slice(bytes);
+        Some(repr)
+    }
+
     /// Attempts to deserialize an element from its uncompressed encoding.
     fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<Self>;
 
     /// Attempts to deserialize an uncompressed element, not checking if the element is in
     /// the correct subgroup.
     ///
+    /// The element is guaranteed to be on the curve; see the
+    /// [validation levels](GroupEncoding#validation-levels).
+    ///
     /// **This is dangerous to call unless you trust the bytes you are reading; otherwise,
     /// API invariants may be broken.** Please consider using
     /// [`UncompressedEncoding::from_uncompressed`] instead.
     fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<Self>;
 
-    /// Converts this element into its uncompressed encoding, so long as it's not
-    /// the point at infinity.
+    /// Attempts to deserialize an uncompressed element, checking that it is on the curve
+    /// but not that it is in the correct subgroup.
+    ///
+    /// **This is dangerous to call unless you check the subgroup membership of the
+    /// element yourself; otherwise, API invariants may be broken.** Please consider using
+    /// [`UncompressedEncoding::from_uncompressed`] instead.
+    ///
+    /// The default implementation calls
+    /// [`UncompressedEncoding::from_uncompressed_unchecked`], which performs exactly these
+    /// checks.
+    fn from_uncompressed_on_curve_only(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        Self::from_uncompressed_unchecked(bytes)
+    }
+
+    /// Converts this element into its uncompressed encoding, representing the identity as
+    /// described in the [trait documentation](UncompressedEncoding#identity).
     fn to_uncompressed(&self) -> Self::Uncompressed;
+
+    /// Converts this element into its uncompressed encoding, returning `None` if the
+    /// encoding does not deserialize back to this element with
+    /// [`UncompressedEncoding::from_uncompressed`].
+    ///
+    /// This catches elements that cannot be encoded faithfully, such as points that were
+    /// deserialized with [`UncompressedEncoding::from_uncompressed_unchecked`] and are not
+    /// in the correct subgroup.
+    fn to_uncompressed_checked(&self) -> CtOption<Self::Uncompressed>
+    where
+        Self: ConditionallySelectable + ConstantTimeEq,
+    {
+        let bytes = self.to_uncompressed();
+        let decoded = Self::from_uncompressed(&bytes);
+        let valid = decoded.is_some() & decoded.unwrap_or(*self).ct_eq(self);
+        CtOption::new(bytes, valid)
+    }
+}
+
+/// Affine representation of a point on an elliptic curve that can be encoded by its
+/// x-coordinate alone, as used by [BIP 340] public keys.
+///
+/// Of the two points sharing an x-coordinate, an x-only encoding implicitly refers to the
+/// one with an even y-coordinate.
+///
+/// [BIP 340]: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+pub trait XOnly: Sized {
+    /// The encoding of the x-coordinate.
+    type XRepr: Copy + Default + Send + Sync + 'static + AsRef<[u8]> + AsMut<[u8]>;
+
+    /// Attempts to deserialize the point with the given x-coordinate and an even
+    /// y-coordinate.
+    fn from_x_bytes_even_y(bytes: &Self::XRepr) -> CtOption<Self>;
+
+    /// Attempts to deserialize a batch of points with the given x-coordinates and even
+    /// y-coordinates.
+    ///
+    /// The default implementation deserializes each point independently; implementations
+    /// may override it to share work between points (for example by batching inversions
+    /// and square roots), which dominates the cost of decompressing large batches.
+    #[cfg(feature = "alloc")]
+    fn batch_from_x_bytes_even_y(xs: &[Self::XRepr]) -> alloc::vec::Vec<CtOption<Self>> {
+        xs.iter().map(Self::from_x_bytes_even_y).collect()
+    }
+
+    /// Converts this element into the encoding of its x-coordinate, discarding the
+    /// parity of its y-coordinate. This may or may not support encoding the identity.
+    fn to_x_bytes(&self) -> Self::XRepr;
+
+    /// Determines if the y-coordinate of this point is even.
+    fn has_even_y(&self) -> Choice;
+}
+
+/// Representation of a point on a Montgomery-form elliptic curve that has a defined
+/// u-coordinate encoding, as used by the X25519 and X448 functions of [RFC 7748].
+///
+/// This encoding is distinct from [`GroupEncoding`]: it does not distinguish a point from
+/// its negation, and the scalars used alongside it are "clamped" before use.
+///
+/// [RFC 7748]: https://www.rfc-editor.org/rfc/rfc7748
+pub trait UCoordinateEncoding: Sized {
+    /// The encoding of the u-coordinate.
+    type URepr: Copy + Default + Send + Sync + 'static + AsRef<[u8]> + AsMut<[u8]>;
+
+    /// The encoding of the scalars that are clamped before use with this curve.
+    type ScalarRepr: Copy + Default + Send + Sync + 'static + AsRef<[u8]> + AsMut<[u8]>;
+
+    /// Attempts to deserialize a point from the encoding of its u-coordinate. Of the two
+    /// points sharing a u-coordinate, either may be returned.
+    fn from_u_bytes(bytes: &Self::URepr) -> CtOption<Self>;
+
+    /// Converts this element into the encoding of its u-coordinate.
+    fn to_u_bytes(&self) -> Self::URepr;
+
+    /// Clamps the given scalar encoding in place, as specified for this curve.
+    ///
+    /// For X25519 this clears the three least-significant bits and the most-significant
+    /// bit, and sets the second most-significant bit. Clamping must be idempotent.
+    fn clamp(scalar: &mut Self::ScalarRepr);
 }
@@ -0,0 +1,205 @@
+//! Macros for implementing the operator traits required by this crate.
+//!
+//! The [`GroupOps`](crate::GroupOps), [`GroupOpsOwned`](crate::GroupOpsOwned),
+//! [`ScalarMul`](crate::ScalarMul) and [`ScalarMulOwned`](crate::ScalarMulOwned) bounds
+//! (and their affine counterparts) require every combination of owned and borrowed
+//! operands. The macros here generate that matrix from a single implementation on two
+//! references:
+//!
+//! ```ignore
+//! impl<'a, 'b> Add<&'b G1Affine> for &'a G1Projective {
+//!     type Output = G1Projective;
+//!
+//!     fn add(self, rhs: &'b G1Affine) -> G1Projective {
+//!         self.add_mixed(rhs)
+//!     }
+//! }
+//!
+//! impl<'a, 'b> Sub<&'b G1Affine> for &'a G1Projective {
+//!     type Output = G1Projective;
+//!
+//!     fn sub(self, rhs: &'b G1Affine) -> G1Projective {
+//!         self + (-rhs)
+//!     }
+//! }
+//!
+//! group::impl_binops_additive!(G1Projective, G1Affine);
+//! ```
+
+/// Implements `Add<Rhs> for Lhs`, `Add<&Rhs> for Lhs` and `Add<Rhs> for &Lhs` with the
+/// given output type, in terms of an existing `Add<&Rhs> for &Lhs` implementation.
+#[macro_export]
+macro_rules! impl_add_binop_specify_output {
+    ($lhs:ty, $rhs:ty, $output:ty) => {
+        impl<'b> ::core::ops::Add<&'b $rhs> for $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn add(self, rhs: &'b $rhs) -> $output {
+                &self + rhs
+            }
+        }
+
+        impl<'a> ::core::ops::Add<$rhs> for &'a $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn add(self, rhs: $rhs) -> $output {
+                self + &rhs
+            }
+        }
+
+        impl ::core::ops::Add<$rhs> for $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn add(self, rhs: $rhs) -> $output {
+                &self + &rhs
+            }
+        }
+    };
+}
+
+/// Implements `Sub<Rhs> for Lhs`, `Sub<&Rhs> for Lhs` and `Sub<Rhs> for &Lhs` with the
+/// given output type, in terms of an existing `Sub<&Rhs> for &Lhs` implementation.
+#[macro_export]
+macro_rules! impl_sub_binop_specify_output {
+    ($lhs:ty, $rhs:ty, $output:ty) => {
+        impl<'b> ::core::ops::Sub<&'b $rhs> for $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn sub(self, rhs: &'b $rhs) -> $output {
+                &self - rhs
+            }
+        }
+
+        impl<'a> ::core::ops::Sub<$rhs> for &'a $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn sub(self, rhs: $rhs) -> $output {
+                self - &rhs
+            }
+        }
+
+        impl ::core::ops::Sub<$rhs> for $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn sub(self, rhs: $rhs) -> $output {
+                &self - &rhs
+            }
+        }
+    };
+}
+
+/// Implements the owned and borrowed additive operators with the given output type, in
+/// terms of existing `Add<&Rhs> for &Lhs` and `Sub<&Rhs> for &Lhs` implementations.
+///
+/// This is suitable for affine representations, whose sums are in the efficient
+/// representation.
+#[macro_export]
+macro_rules! impl_binops_additive_specify_output {
+    ($lhs:ty, $rhs:ty, $output:ty) => {
+        $crate::impl_add_binop_specify_output!($lhs, $rhs, $output);
+        $crate::impl_sub_binop_specify_output!($lhs, $rhs, $output);
+    };
+}
+
+/// Implements the owned and borrowed additive operators, including `AddAssign` and
+/// `SubAssign`, in terms of existing `Add<&Rhs> for &Lhs` and `Sub<&Rhs> for &Lhs`
+/// implementations with output `Lhs`.
+#[macro_export]
+macro_rules! impl_binops_additive {
+    ($lhs:ty, $rhs:ty) => {
+        $crate::impl_binops_additive_specify_output!($lhs, $rhs, $lhs);
+
+        impl ::core::ops::SubAssign<$rhs> for $lhs {
+            #[inline]
+            fn sub_assign(&mut self, rhs: $rhs) {
+                *self = &*self - &rhs;
+            }
+        }
+
+        impl ::core::ops::AddAssign<$rhs> for $lhs {
+            #[inline]
+            fn add_assign(&mut self, rhs: $rhs) {
+                *self = &*self + &rhs;
+            }
+        }
+
+        impl<'b> ::core::ops::SubAssign<&'b $rhs> for $lhs {
+            #[inline]
+            fn sub_assign(&mut self, rhs: &'b $rhs) {
+                *self = &*self - rhs;
+            }
+        }
+
+        impl<'b> ::core::ops::AddAssign<&'b $rhs> for $lhs {
+            #[inline]
+            fn add_assign(&mut self, rhs: &'b $rhs) {
+                *self = &*self + rhs;
+            }
+        }
+    };
+}
+
+/// Implements `Mul<Rhs> for Lhs`, `Mul<&Rhs> for Lhs` and `Mul<Rhs> for &Lhs` with the
+/// given output type, in terms of an existing `Mul<&Rhs> for &Lhs` implementation.
+///
+/// This is suitable for scalar multiplication of affine representations.
+#[macro_export]
+macro_rules! impl_binops_multiplicative_mixed {
+    ($lhs:ty, $rhs:ty, $output:ty) => {
+        impl<'b> ::core::ops::Mul<&'b $rhs> for $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn mul(self, rhs: &'b $rhs) -> $output {
+                &self * rhs
+            }
+        }
+
+        impl<'a> ::core::ops::Mul<$rhs> for &'a $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn mul(self, rhs: $rhs) -> $output {
+                self * &rhs
+            }
+        }
+
+        impl ::core::ops::Mul<$rhs> for $lhs {
+            type Output = $output;
+
+            #[inline]
+            fn mul(self, rhs: $rhs) -> $output {
+                &self * &rhs
+            }
+        }
+    };
+}
+
+/// Implements the owned and borrowed multiplicative operators, including `MulAssign`, in
+/// terms of an existing `Mul<&Rhs> for &Lhs` implementation with output `Lhs`.
+#[macro_export]
+macro_rules! impl_binops_multiplicative {
+    ($lhs:ty, $rhs:ty) => {
+        $crate::impl_binops_multiplicative_mixed!($lhs, $rhs, $lhs);
+
+        impl ::core::ops::MulAssign<$rhs> for $lhs {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $rhs) {
+                *self = &*self * &rhs;
+            }
+        }
+
+        impl<'b> ::core::ops::MulAssign<&'b $rhs> for $lhs {
+            #[inline]
+            fn mul_assign(&mut self, rhs: &'b $rhs) {
+                *self = &*self * rhs;
+            }
+        }
+    };
+}
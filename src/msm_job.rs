@@ -0,0 +1,300 @@
+//! A stable wire format for shipping multi-scalar multiplications to external provers.
+//!
+//! Provers often offload large multi-scalar multiplications to another process or to a
+//! GPU. [`MsmJob`] fixes one binary layout for the bases and scalars of such a
+//! computation, so that both sides agree on it without a bespoke format per project:
+//!
+//! | Bytes         | Contents                                                   |
+//! |---------------|------------------------------------------------------------|
+//! | `0..4`        | [`TaggedEncoding::CURVE_ID`] of the affine bases           |
+//! | `4`           | Format version ([`VERSION`])                               |
+//! | `5`           | Flags ([`FLAG_UNCOMPRESSED`], [`FLAG_SCALARS_BIG_ENDIAN`]) |
+//! | `6..14`       | The number of terms `n`, as a little-endian `u64`          |
+//! | `14..`        | `n` encoded bases, followed by `n` encoded scalars         |
+//!
+//! Bases are encoded with [`GroupEncoding`], or with [`UncompressedEncoding`] if
+//! [`FLAG_UNCOMPRESSED`] is set. Scalars are encoded as canonical integers of the length
+//! of [`PrimeField::Repr`], in little-endian byte order unless
+//! [`FLAG_SCALARS_BIG_ENDIAN`] is set. Storing all bases before all scalars lets
+//! consumers copy each array to a device in one transfer.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use ff::PrimeField;
+
+use crate::{envelope::TaggedEncoding, prime::PrimeCurve, GroupEncoding, UncompressedEncoding};
+
+/// The current version of the format.
+pub const VERSION: u8 = 1;
+
+/// The length of the header preceding the bases.
+pub const HEADER_LEN: usize = 14;
+
+/// Flag indicating that the bases use their uncompressed encoding.
+pub const FLAG_UNCOMPRESSED: u8 = 0x01;
+
+/// Flag indicating that the scalars are encoded in big-endian byte order.
+pub const FLAG_SCALARS_BIG_ENDIAN: u8 = 0x02;
+
+/// The encoding options of an [`MsmJob`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MsmFormat {
+    /// Whether the bases use their uncompressed encoding, trading size for decoding
+    /// speed.
+    pub uncompressed: bool,
+    /// Whether the scalars are encoded in big-endian byte order.
+    pub scalars_big_endian: bool,
+}
+
+impl MsmFormat {
+    fn flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.uncompressed {
+            flags |= FLAG_UNCOMPRESSED;
+        }
+        if self.scalars_big_endian {
+            flags |= FLAG_SCALARS_BIG_ENDIAN;
+        }
+        flags
+    }
+
+    fn from_flags(flags: u8) -> Option<Self> {
+        if flags & !(FLAG_UNCOMPRESSED | FLAG_SCALARS_BIG_ENDIAN) != 0 {
+            return None;
+        }
+        Some(MsmFormat {
+            uncompressed: flags & FLAG_UNCOMPRESSED != 0,
+            scalars_big_endian: flags & FLAG_SCALARS_BIG_ENDIAN != 0,
+        })
+    }
+}
+
+/// The inputs of a multi-scalar multiplication `sum(scalars[i] · bases[i])`.
+pub struct MsmJob<C: PrimeCurve> {
+    /// The bases of the multi-scalar multiplication.
+    pub bases: Vec<C::Affine>,
+    /// The scalars of the multi-scalar multiplication.
+    pub scalars: Vec<C::Scalar>,
+}
+
+impl<C: PrimeCurve> Clone for MsmJob<C> {
+    fn clone(&self) -> Self {
+        MsmJob {
+            bases: self.bases.clone(),
+            scalars: self.scalars.clone(),
+        }
+    }
+}
+
+impl<C: PrimeCurve> fmt::Debug for MsmJob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MsmJob")
+            .field("bases", &self.bases)
+            .field("scalars", &self.scalars)
+            .finish()
+    }
+}
+
+/// Returns whether `F::Repr` holds its integer in big-endian byte order.
+fn repr_is_big_endian<F: PrimeField>() -> bool {
+    F::ONE.to_repr().as_ref()[0] != 1
+}
+
+impl<C: PrimeCurve> MsmJob<C> {
+    /// Creates a job from its bases and scalars. This function will panic if
+    /// `bases.len() != scalars.len()`.
+    pub fn new(bases: Vec<C::Affine>, scalars: Vec<C::Scalar>) -> Self {
+        assert_eq!(bases.len(), scalars.len());
+        MsmJob { bases, scalars }
+    }
+
+    /// Returns the number of terms in this job.
+    pub fn len(&self) -> usize {
+        self.bases.len()
+    }
+
+    /// Returns `true` if this job has no terms.
+    pub fn is_empty(&self) -> bool {
+        self.bases.is_empty()
+    }
+
+    /// Computes the result of this job in-process, as a reference for the results
+    /// returned by external provers.
+    pub fn evaluate(&self) -> C {
+        assert_eq!(self.bases.len(), self.scalars.len());
+        self.bases
+            .iter()
+            .zip(self.scalars.iter())
+            .fold(C::identity(), |acc, (base, scalar)| acc + *base * scalar)
+    }
+}
+
+impl<C: PrimeCurve> MsmJob<C>
+where
+    C::Affine: TaggedEncoding + UncompressedEncoding,
+{
+    /// Encodes this job in the given format. This function will panic if
+    /// `self.bases.len() != self.scalars.len()`.
+    pub fn to_bytes(&self, format: MsmFormat) -> Vec<u8> {
+        assert_eq!(self.bases.len(), self.scalars.len());
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.len() * Self::term_len(format));
+        bytes.extend_from_slice(&C::Affine::CURVE_ID);
+        bytes.push(VERSION);
+        bytes.push(format.flags());
+        bytes.extend_from_slice(&(self.len() as u64).to_le_bytes());
+
+        for base in &self.bases {
+            if format.uncompressed {
+                bytes.extend_from_slice(base.to_uncompressed().as_ref());
+            } else {
+                bytes.extend_from_slice(base.to_bytes().as_ref());
+            }
+        }
+
+        let reverse = format.scalars_big_endian != repr_is_big_endian::<C::Scalar>();
+        for scalar in &self.scalars {
+            let start = bytes.len();
+            bytes.extend_from_slice(scalar.to_repr().as_ref());
+            if reverse {
+                bytes[start..].reverse();
+            }
+        }
+
+        bytes
+    }
+
+    /// Decodes a job, checking that the header matches this curve and that every base
+    /// and scalar is valid. Returns the job and the format it was encoded in.
+    ///
+    /// This is not constant time, and is intended for public inputs.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, MsmFormat), MsmJobError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(MsmJobError::Truncated);
+        }
+
+        let (header, payload) = bytes.split_at(HEADER_LEN);
+        let mut curve_id = [0; 4];
+        curve_id.copy_from_slice(&header[..4]);
+        if curve_id != C::Affine::CURVE_ID {
+            return Err(MsmJobError::CurveMismatch {
+                expected: C::Affine::CURVE_ID,
+                found: curve_id,
+            });
+        }
+        if header[4] != VERSION {
+            return Err(MsmJobError::UnsupportedVersion(header[4]));
+        }
+        let format =
+            MsmFormat::from_flags(header[5]).ok_or(MsmJobError::UnsupportedFlags(header[5]))?;
+
+        let mut len = [0; 8];
+        len.copy_from_slice(&header[6..]);
+        let len = usize::try_from(u64::from_le_bytes(len)).map_err(|_| MsmJobError::Truncated)?;
+        let point_len = Self::point_len(format);
+        let bases_len = len.checked_mul(point_len).ok_or(MsmJobError::Truncated)?;
+        match len
+            .checked_mul(Self::term_len(format))
+            .map(|expected| payload.len().cmp(&expected))
+        {
+            Some(core::cmp::Ordering::Equal) => (),
+            Some(core::cmp::Ordering::Greater) => return Err(MsmJobError::TrailingBytes),
+            _ => return Err(MsmJobError::Truncated),
+        }
+
+        let (base_bytes, scalar_bytes) = payload.split_at(bases_len);
+        let bases = base_bytes
+            .chunks(point_len)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let base = if format.uncompressed {
+                    let mut repr = <C::Affine as UncompressedEncoding>::Uncompressed::default();
+                    repr.as_mut().copy_from_slice(chunk);
+                    C::Affine::from_uncompressed(&repr)
+                } else {
+                    let mut repr = <C::Affine as GroupEncoding>::Repr::default();
+                    repr.as_mut().copy_from_slice(chunk);
+                    C::Affine::from_bytes(&repr)
+                };
+                Option::from(base).ok_or(MsmJobError::InvalidBase(i))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let reverse = format.scalars_big_endian != repr_is_big_endian::<C::Scalar>();
+        let scalars = scalar_bytes
+            .chunks(Self::scalar_len())
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut repr = <C::Scalar as PrimeField>::Repr::default();
+                repr.as_mut().copy_from_slice(chunk);
+                if reverse {
+                    repr.as_mut().reverse();
+                }
+                Option::from(C::Scalar::from_repr(repr)).ok_or(MsmJobError::InvalidScalar(i))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((MsmJob { bases, scalars }, format))
+    }
+
+    fn point_len(format: MsmFormat) -> usize {
+        if format.uncompressed {
+            <C::Affine as UncompressedEncoding>::Uncompressed::default()
+                .as_ref()
+                .len()
+        } else {
+            <C::Affine as GroupEncoding>::Repr::default().as_ref().len()
+        }
+    }
+
+    fn scalar_len() -> usize {
+        <C::Scalar as PrimeField>::Repr::default().as_ref().len()
+    }
+
+    fn term_len(format: MsmFormat) -> usize {
+        Self::point_len(format) + Self::scalar_len()
+    }
+}
+
+/// The reasons an encoded [`MsmJob`] can fail to decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MsmJobError {
+    /// The encoding is shorter than its header claims.
+    Truncated,
+    /// The encoding is longer than its header claims.
+    TrailingBytes,
+    /// The job holds elements of a different curve.
+    CurveMismatch { expected: [u8; 4], found: [u8; 4] },
+    /// The job was produced by an unsupported version of the format.
+    UnsupportedVersion(u8),
+    /// The job has flags set that this version of the format does not define.
+    UnsupportedFlags(u8),
+    /// The base at the given index is not a valid encoding of a curve point.
+    InvalidBase(usize),
+    /// The scalar at the given index is not a canonical encoding of a scalar.
+    InvalidScalar(usize),
+}
+
+impl fmt::Display for MsmJobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MsmJobError::Truncated => f.write_str("the job is truncated"),
+            MsmJobError::TrailingBytes => f.write_str("the job has trailing bytes"),
+            MsmJobError::CurveMismatch { expected, found } => write!(
+                f,
+                "expected a job with curve id {:02x?}, found curve id {:02x?}",
+                expected, found
+            ),
+            MsmJobError::UnsupportedVersion(version) => {
+                write!(f, "unsupported job version {}", version)
+            }
+            MsmJobError::UnsupportedFlags(flags) => {
+                write!(f, "unsupported job flags {:#04x}", flags)
+            }
+            MsmJobError::InvalidBase(i) => write!(f, "base {} is not a valid element", i),
+            MsmJobError::InvalidScalar(i) => write!(f, "scalar {} is not a valid scalar", i),
+        }
+    }
+}
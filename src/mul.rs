@@ -0,0 +1,203 @@
+//! Generic scalar multiplication algorithms.
+//!
+//! These are building blocks for curve implementations that do not yet have a
+//! specialized scalar multiplication. They only use the group operations, and so work
+//! with any addition formulas.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{
+    util::{joint_sparse_form, scalar_windows},
+    Group, GroupEncoding,
+};
+
+/// Multiplies `p` by `scalar` with a Montgomery ladder, in constant time.
+///
+/// The ladder maintains the invariant `R1 - R0 = P`, and performs exactly one addition
+/// and one doubling for each of the `NUM_BITS` bits of the scalar field, regardless of
+/// the value of each bit. The only data-dependent operations are conditional swaps
+/// performed with [`ConditionallySelectable`], so neither the sequence of group
+/// operations nor the memory access pattern depends on the scalar. This is the
+/// appropriate default for multiplications by secret scalars, such as key derivation
+/// and signing.
+///
+/// Timing is only independent of the scalar if the group operations themselves are
+/// constant time. In particular, `R0` starts at the identity and `R0 = R1` is possible
+/// when `P` is the identity, so the addition formulas must handle these cases without
+/// branching (as complete formulas do).
+///
+/// The scalar is read from its [`PrimeField::to_repr`](ff::PrimeField::to_repr)
+/// encoding, which is assumed to be little-endian.
+pub fn ladder<G: Group + ConditionallySelectable>(p: &G, scalar: &G::Scalar) -> G {
+    cross_check(p, scalar, ladder_inner(p, scalar).0)
+}
+
+/// Multiplies `p` by `scalar` with a Montgomery ladder, in constant time, and verifies
+/// the result as a countermeasure against fault injection.
+///
+/// After running [`ladder`], this checks that the ladder invariant `R1 - R0 = P` still
+/// holds, and that the result survives a round-trip through its validating
+/// [`GroupEncoding`], which rejects points that are not on the curve. A fault injected
+/// into the computation (for example by glitching a smartcard) is likely to break one
+/// of these checks, in which case `None` is returned instead of the faulty result, which
+/// could otherwise leak information about the scalar.
+///
+/// The identity is always accepted by the round-trip check, even for encodings that
+/// cannot represent it, because it is the correct result when `p` is the identity or the
+/// scalar is zero. The invariant check still applies in that case.
+///
+/// These checks add roughly the cost of one addition and one point decompression.
+pub fn mul_checked<G>(p: &G, scalar: &G::Scalar) -> CtOption<G>
+where
+    G: Group + ConditionallySelectable + ConstantTimeEq + GroupEncoding,
+{
+    let (r0, r1) = ladder_inner(p, scalar);
+
+    let invariant = (r1 - r0).ct_eq(p);
+    trace!(ADDITIONS);
+    let decoded = G::from_bytes(&r0.to_bytes());
+    let on_curve =
+        r0.is_identity() | (decoded.is_some() & decoded.unwrap_or(G::identity()).ct_eq(&r0));
+
+    CtOption::new(r0, invariant & on_curve)
+}
+
+/// Multiplies `p` by the unreduced integer with the given little-endian `u64` limbs, in
+/// constant time.
+///
+/// This allows callers holding wide integers (such as the output of a hash or a key
+/// derivation function) to multiply by them without first reducing them into the scalar
+/// field. The integer is processed with the same Montgomery ladder as [`ladder`], over
+/// all `64 * limbs.len()` of its bits; only the number of limbs is treated as public.
+pub fn mul_by_limbs<G: Group + ConditionallySelectable>(p: &G, limbs: &[u64]) -> G {
+    let bits = (0..64 * limbs.len())
+        .rev()
+        .map(|i| Choice::from(((limbs[i / 64] >> (i % 64)) & 1) as u8));
+    ladder_bits(p, bits).0
+}
+
+/// Multiplies `p` by `scalar` with textbook double-and-add, in variable time.
+///
+/// This is a slow reference implementation, with no dependencies beyond the group
+/// operations, against which optimized scalar multiplications can be checked. It must
+/// not be used with secret scalars.
+///
+/// The scalar is read from its [`PrimeField::to_repr`](ff::PrimeField::to_repr)
+/// encoding, which is assumed to be little-endian.
+pub fn mul_reference<G: Group>(p: &G, scalar: &G::Scalar) -> G {
+    let mut acc = G::identity();
+    for bit in scalar_windows(scalar, 1).rev() {
+        acc = acc.double();
+        if bit == 1 {
+            acc += p;
+        }
+    }
+    acc
+}
+
+/// Computes `[a] P + [b] Q` with Shamir's trick, in variable time.
+///
+/// This is the shape of ECDSA-style verification equations `[u1] G + [u2] Q`. The two
+/// multiplications share a single chain of doublings over the
+/// [`joint_sparse_form`] of the scalars, with one addition of `±P`, `±Q`, `±(P + Q)` or
+/// `±(P - Q)` for each position in which either digit is non-zero. On average half of
+/// the positions are non-zero, compared to three quarters of the bit positions of the
+/// plain binary scalars. It must not be used with secret scalars.
+///
+/// This only uses the group operations. Curves with an efficient endomorphism can
+/// compute the same sum faster by splitting both scalars GLV-style, which this crate
+/// cannot do generically; they should provide their own implementation.
+///
+/// The scalars are read from their [`PrimeField::to_repr`](ff::PrimeField::to_repr)
+/// encodings, which are assumed to be little-endian.
+pub fn double_scalar_mul_vartime<G: Group>(a: &G::Scalar, p: &G, b: &G::Scalar, q: &G) -> G {
+    let p_plus_q = *p + q;
+    let p_minus_q = *p - q;
+    trace!(ADDITIONS, 2);
+
+    let mut acc = G::identity();
+    for digits in joint_sparse_form(a, b).rev() {
+        acc = acc.double();
+        trace!(DOUBLINGS);
+
+        match digits {
+            (0, 0) => continue,
+            (1, 0) => acc += p,
+            (-1, 0) => acc -= p,
+            (0, 1) => acc += q,
+            (0, -1) => acc -= q,
+            (1, 1) => acc += p_plus_q,
+            (-1, -1) => acc -= p_plus_q,
+            (1, -1) => acc += p_minus_q,
+            _ => acc -= p_minus_q,
+        }
+        trace!(ADDITIONS);
+    }
+    acc
+}
+
+/// The number of calls to [`cross_check`] between consecutive checks.
+#[cfg(feature = "mul-cross-check")]
+pub const CROSS_CHECK_INTERVAL: usize = 16;
+
+/// Returns `result`, which should equal `[scalar] p`.
+///
+/// When the `mul-cross-check` feature flag is enabled, one in every
+/// `CROSS_CHECK_INTERVAL` calls recomputes the product with [`mul_reference`], and
+/// panics if it differs from `result`. Curve implementations can pass the results of
+/// their `Mul` implementations through this function, so that miscompilations and
+/// formula bugs trip during downstream integration testing. When the feature flag is
+/// disabled, this does nothing.
+///
+/// The check runs in variable time, so the feature flag must not be enabled in
+/// production.
+#[inline]
+#[allow(unused_variables)]
+pub fn cross_check<G: Group>(p: &G, scalar: &G::Scalar, result: G) -> G {
+    #[cfg(feature = "mul-cross-check")]
+    {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        if CALLS.fetch_add(1, Ordering::Relaxed) % CROSS_CHECK_INTERVAL == 0 {
+            assert!(
+                mul_reference(p, scalar) == result,
+                "scalar multiplication does not match the reference implementation"
+            );
+        }
+    }
+    result
+}
+
+/// Runs the Montgomery ladder, returning `(R0, R1) = ([scalar]P, [scalar + 1]P)`.
+fn ladder_inner<G: Group + ConditionallySelectable>(p: &G, scalar: &G::Scalar) -> (G, G) {
+    let bits = scalar_windows(scalar, 1)
+        .rev()
+        .map(|bit| Choice::from(bit as u8));
+    ladder_bits(p, bits)
+}
+
+/// Runs the Montgomery ladder over the given bits, most-significant first, returning
+/// `(R0, R1) = ([k]P, [k + 1]P)` where `k` is the integer they represent.
+fn ladder_bits<G, I>(p: &G, bits: I) -> (G, G)
+where
+    G: Group + ConditionallySelectable,
+    I: Iterator<Item = Choice>,
+{
+    let mut r0 = G::identity();
+    let mut r1 = *p;
+    let mut swap = Choice::from(0);
+    for bit in bits {
+        // Swap lazily, only when the current bit differs from the previous one.
+        G::conditional_swap(&mut r0, &mut r1, swap ^ bit);
+        swap = bit;
+
+        r1 += r0;
+        r0 = r0.double();
+        trace!(ADDITIONS);
+        trace!(DOUBLINGS);
+    }
+    G::conditional_swap(&mut r0, &mut r1, swap);
+
+    (r0, r1)
+}
@@ -0,0 +1,165 @@
+//! A wrapper for group elements that are statically known not to be the identity.
+
+use core::ops::{Add, Deref, Mul, Neg, Sub};
+
+use rand_core::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{Curve, Group, GroupEncoding};
+
+/// A group element that is not the identity.
+///
+/// Public keys, Diffie–Hellman shares and the outputs of hash-to-curve are never the
+/// identity in a correct protocol run, and accepting the identity in their place is a
+/// common source of vulnerabilities. APIs can take or return `NonIdentity<G>` to encode
+/// this invariant in their types; the wrapped element is available through
+/// [`Deref`] and [`NonIdentity::get`].
+///
+/// Arithmetic on `NonIdentity` elements returns the underlying group type, as the result
+/// may be the identity; negation preserves the invariant, and returns a `NonIdentity`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonIdentity<P> {
+    point: P,
+}
+
+impl<G: Group> NonIdentity<G> {
+    /// Wraps `point`, or returns `None` if it is the identity.
+    pub fn new(point: G) -> CtOption<Self> {
+        CtOption::new(NonIdentity { point }, !point.is_identity())
+    }
+
+    /// Returns an element chosen uniformly at random from the non-identity elements of
+    /// the group, as with [`GroupCore::random`](crate::GroupCore::random).
+    pub fn random(rng: impl RngCore) -> Self {
+        NonIdentity {
+            point: G::random(rng),
+        }
+    }
+
+    /// Returns the fixed generator of the group.
+    pub fn generator() -> Self {
+        NonIdentity {
+            point: G::generator(),
+        }
+    }
+
+    /// Wraps the result of decoding an element, rejecting the identity.
+    ///
+    /// This only branches on whether decoding succeeded, which is not secret.
+    fn from_decoded(decoded: CtOption<G>) -> CtOption<Self> {
+        match Option::from(decoded) {
+            Some(point) => Self::new(point),
+            None => CtOption::new(Self::generator(), Choice::from(0)),
+        }
+    }
+}
+
+impl<P: Copy> NonIdentity<P> {
+    /// Returns the wrapped element.
+    pub fn get(&self) -> P {
+        self.point
+    }
+}
+
+impl<C: Curve> NonIdentity<C> {
+    /// Converts the wrapped element into its affine representation.
+    pub fn to_affine(&self) -> NonIdentity<C::AffineRepr> {
+        NonIdentity {
+            point: self.point.to_affine(),
+        }
+    }
+}
+
+impl<P> Deref for NonIdentity<P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        &self.point
+    }
+}
+
+impl<P> AsRef<P> for NonIdentity<P> {
+    fn as_ref(&self) -> &P {
+        &self.point
+    }
+}
+
+impl<P: ConstantTimeEq> ConstantTimeEq for NonIdentity<P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.point.ct_eq(&other.point)
+    }
+}
+
+impl<P: ConditionallySelectable> ConditionallySelectable for NonIdentity<P> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        NonIdentity {
+            point: P::conditional_select(&a.point, &b.point, choice),
+        }
+    }
+}
+
+/// Deserializes elements as with the underlying [`GroupEncoding`], additionally
+/// rejecting the encoding of the identity.
+impl<G: Group + GroupEncoding> GroupEncoding for NonIdentity<G> {
+    type Repr = G::Repr;
+
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+        Self::from_decoded(G::from_bytes(bytes))
+    }
+
+    fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+        Self::from_decoded(G::from_bytes_unchecked(bytes))
+    }
+
+    fn to_bytes(&self) -> Self::Repr {
+        self.point.to_bytes()
+    }
+}
+
+impl<G: Group> Neg for NonIdentity<G> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        NonIdentity { point: -self.point }
+    }
+}
+
+impl<G: Group> Add for NonIdentity<G> {
+    type Output = G;
+
+    fn add(self, other: Self) -> G {
+        self.point + other.point
+    }
+}
+
+impl<G: Group> Add<G> for NonIdentity<G> {
+    type Output = G;
+
+    fn add(self, other: G) -> G {
+        self.point + other
+    }
+}
+
+impl<G: Group> Sub for NonIdentity<G> {
+    type Output = G;
+
+    fn sub(self, other: Self) -> G {
+        self.point - other.point
+    }
+}
+
+impl<G: Group> Sub<G> for NonIdentity<G> {
+    type Output = G;
+
+    fn sub(self, other: G) -> G {
+        self.point - other
+    }
+}
+
+impl<G: Group> Mul<G::Scalar> for NonIdentity<G> {
+    type Output = G;
+
+    fn mul(self, scalar: G::Scalar) -> G {
+        self.point * scalar
+    }
+}
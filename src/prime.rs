@@ -1,24 +1,58 @@
 use core::fmt;
-use core::ops::{Mul, Neg};
-use ff::PrimeField;
-use subtle::Choice;
+use core::ops::Neg;
+use ff::{Field, PrimeField};
+use rand_core::RngCore;
+use subtle::{Choice, ConstantTimeEq, CtOption};
 
-use crate::{Curve, Group, GroupEncoding};
+use crate::{
+    AffineGroupOps, AffineGroupOpsOwned, AffineScalarMul, Curve, Group, GroupCore, GroupEncoding,
+};
 
 /// This trait represents an element of a prime-order cryptographic group.
-pub trait PrimeGroup: Group + GroupEncoding {}
+pub trait PrimeGroup: Group + GroupEncoding {
+    /// Returns an element chosen uniformly at random from the whole group, including the
+    /// identity.
+    ///
+    /// [`GroupCore::random`] never returns the identity, which makes its output
+    /// distribution (negligibly) non-uniform over the group. Simulation-based security
+    /// proofs and some tests require the truly uniform distribution provided here.
+    ///
+    /// This function is non-deterministic, and samples from the user-provided RNG.
+    ///
+    /// [`GroupCore::random`]: crate::GroupCore::random
+    fn random_including_identity(rng: impl RngCore) -> Self {
+        Self::generator() * Self::Scalar::random(rng)
+    }
+}
 
 /// Efficient representation of an elliptic curve point guaranteed to be
 /// in the correct prime order subgroup.
+///
+/// Generic code can mix the efficient and affine representations, with mixed addition
+/// and multiplication of affine points producing efficient ones:
+///
+/// ```
+/// use group::{prime::PrimeCurve, Curve};
+///
+/// fn linear_combination<C: PrimeCurve>(bases: &[C::Affine], scalars: &[C::Scalar]) -> C::Affine {
+///     bases
+///         .iter()
+///         .zip(scalars)
+///         .fold(C::identity(), |acc, (base, scalar)| acc + *base * scalar)
+///         .to_affine()
+/// }
+/// ```
 pub trait PrimeCurve: Curve<AffineRepr = <Self as PrimeCurve>::Affine> + PrimeGroup {
-    type Affine: PrimeCurveAffine<Curve = Self, Scalar = Self::Scalar>
-        + Mul<Self::Scalar, Output = Self>
-        + for<'r> Mul<&'r Self::Scalar, Output = Self>;
+    type Affine: PrimeCurveAffine<Curve = Self, Scalar = Self::Scalar>;
 }
 
 /// Affine representation of an elliptic curve point guaranteed to be
 /// in the correct prime order subgroup.
-pub trait PrimeCurveAffine: GroupEncoding
+///
+/// Points that may be derived from secret data should be compared with
+/// [`ConstantTimeEq::ct_eq`] rather than `==`.
+pub trait PrimeCurveAffine:
+    GroupEncoding
     + Copy
     + Clone
     + Sized
@@ -27,10 +61,14 @@ pub trait PrimeCurveAffine: GroupEncoding
     + fmt::Debug
     + PartialEq
     + Eq
+    + ConstantTimeEq
     + 'static
     + Neg<Output = Self>
-    + Mul<<Self as PrimeCurveAffine>::Scalar, Output = <Self as PrimeCurveAffine>::Curve>
-    + for<'r> Mul<&'r <Self as PrimeCurveAffine>::Scalar, Output = <Self as PrimeCurveAffine>::Curve>
+    + AffineScalarMul<<Self as PrimeCurveAffine>::Scalar, <Self as PrimeCurveAffine>::Curve>
+    + AffineGroupOps<Self, <Self as PrimeCurveAffine>::Curve>
+    + AffineGroupOpsOwned<Self, <Self as PrimeCurveAffine>::Curve>
+    + AffineGroupOps<<Self as PrimeCurveAffine>::Curve, <Self as PrimeCurveAffine>::Curve>
+    + AffineGroupOpsOwned<<Self as PrimeCurveAffine>::Curve, <Self as PrimeCurveAffine>::Curve>
 {
     type Scalar: PrimeField;
     type Curve: PrimeCurve<Affine = Self, Scalar = Self::Scalar>;
@@ -47,4 +85,15 @@ pub trait PrimeCurveAffine: GroupEncoding
 
     /// Converts this element to its curve representation.
     fn to_curve(&self) -> Self::Curve;
+
+    /// Returns `self + [t] G`, where `G` is the fixed generator, or `None` if the result
+    /// is the identity.
+    ///
+    /// This is the core operation of hierarchical key derivation (BIP 32) and Taproot
+    /// commitments (BIP 341). The multiplication uses the curve's generator, so
+    /// implementations with precomputed generator tables benefit automatically.
+    fn tweak_add(&self, t: &Self::Scalar) -> CtOption<Self> {
+        let result = (<Self::Curve as GroupCore>::generator() * t + self).to_affine();
+        CtOption::new(result, !result.is_identity())
+    }
 }
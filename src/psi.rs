@@ -0,0 +1,84 @@
+//! The untwist-Frobenius-twist endomorphism of pairing-friendly twist groups.
+
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::Group;
+
+/// An elliptic curve group defined over an extension field as a twist of a curve over the
+/// base field, equipped with the untwist-Frobenius-twist endomorphism `ψ`.
+///
+/// This is the `G2` group of most pairing-friendly curves. Unlike a GLV endomorphism, `ψ`
+/// is not cheap to express as a scalar multiplication with a small eigenvalue, but it is
+/// the key ingredient in fast subgroup checks and cofactor clearing for these groups.
+pub trait Psi: Group {
+    /// Applies the endomorphism `ψ` to this element.
+    fn psi(&self) -> Self;
+
+    /// Applies the endomorphism `ψ²` to this element.
+    ///
+    /// The default implementation applies [`Psi::psi`] twice; implementations should
+    /// override it when `ψ²` has a cheaper closed form.
+    fn psi2(&self) -> Self {
+        self.psi().psi()
+    }
+}
+
+/// The `G2` group of a BLS12 curve, whose subgroup check and cofactor clearing can be
+/// performed with `ψ`.
+///
+/// Implementations of [`CofactorGroup`](crate::cofactor::CofactorGroup) for such groups
+/// can delegate `is_torsion_free` and `clear_cofactor` to the provided methods of this
+/// trait.
+pub trait Bls12G2: Psi + ConstantTimeEq {
+    /// The absolute value of the curve parameter `x`.
+    const X: u64;
+
+    /// Whether the curve parameter `x` is negative.
+    const X_IS_NEGATIVE: bool;
+
+    /// Multiplies this element by the curve parameter `x`.
+    ///
+    /// The curve parameter is public, so this runs in time dependent only on `x`. The
+    /// default implementation uses plain double-and-add; implementations can override it
+    /// with a shorter [`AdditionChain`](crate::addition_chain::AdditionChain) for `|x|`.
+    fn mul_by_x(&self) -> Self {
+        let mut acc = Self::identity();
+        for i in (0..64 - Self::X.leading_zeros()).rev() {
+            acc = acc.double();
+            trace!(DOUBLINGS);
+            if (Self::X >> i) & 1 == 1 {
+                acc += *self;
+                trace!(ADDITIONS);
+            }
+        }
+
+        if Self::X_IS_NEGATIVE {
+            -acc
+        } else {
+            acc
+        }
+    }
+
+    /// Determines if this element is in the prime-order subgroup, by checking that
+    /// `ψ(P) = [x]P` ([Scott 2021](https://eprint.iacr.org/2021/1130)).
+    fn is_torsion_free_psi(&self) -> Choice {
+        self.psi().ct_eq(&self.mul_by_x())
+    }
+
+    /// Maps this element to the prime-order subgroup, by multiplying it by
+    /// `h(ψ) = x² - x - 1 + (x - 1)ψ + 2ψ²`
+    /// ([Budroni–Pintore](https://eprint.iacr.org/2017/419)).
+    fn clear_cofactor_psi(&self) -> Self {
+        trace!(ADDITIONS, 5);
+        trace!(DOUBLINGS);
+
+        let t1 = self.mul_by_x(); // [x] P
+        let t2 = self.psi(); // ψ(P)
+
+        self.double().psi2() // ψ²(2P)
+            + (t1 + t2).mul_by_x() // ψ²(2P) + [x²] P + [x] ψ(P)
+            - t1 // ψ²(2P) + [x² - x] P + [x] ψ(P)
+            - t2 // ψ²(2P) + [x² - x] P + [x - 1] ψ(P)
+            - *self // ψ²(2P) + [x² - x - 1] P + [x - 1] ψ(P)
+    }
+}
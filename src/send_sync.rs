@@ -0,0 +1,53 @@
+//! Compile-time assertions that precomputed tables and batch containers are
+//! `Send + Sync`.
+//!
+//! Proving pipelines share these structures between threads, so losing either auto trait
+//! (for example by adding an `Rc` or a raw pointer field) is a breaking change. The
+//! functions below are never called; they only fail to compile if one of the types
+//! stops implementing `Send + Sync` for some choice of its type parameters.
+
+#![allow(dead_code)]
+
+use ff::PrimeField;
+use subtle::ConditionallySelectable;
+
+use crate::{
+    table::{LookupTable, OddLookupTable},
+    Group,
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn tables<G: Group + ConditionallySelectable>() {
+    assert_send_sync::<LookupTable<G, 8>>();
+    assert_send_sync::<OddLookupTable<G, 8>>();
+    assert_send_sync::<crate::commitment::PedersenGens<G>>();
+}
+
+fn scalars<F: PrimeField>() {
+    assert_send_sync::<crate::util::ScalarWindows<F>>();
+    assert_send_sync::<crate::util::JointSparseForm<F>>();
+}
+
+#[cfg(feature = "alloc")]
+fn alloc_tables<G: Group, C: crate::prime::PrimeCurve, F: PrimeField, P: crate::XOnly>() {
+    use alloc::vec::Vec;
+
+    use crate::{AffineWnafBase, Wnaf, WnafBase, WnafScalar};
+
+    assert_send_sync::<Wnaf<(), Vec<G>, Vec<i64>>>();
+    assert_send_sync::<Wnaf<usize, &[G], &[i64]>>();
+    assert_send_sync::<WnafBase<G, 4>>();
+    assert_send_sync::<WnafScalar<F, 4>>();
+    assert_send_sync::<AffineWnafBase<C, 4>>();
+    assert_send_sync::<crate::accumulator::Accumulator<C>>();
+    assert_send_sync::<crate::compressed::CompressedBatch<P>>();
+    assert_send_sync::<crate::compressed::CompressedPoints<C>>();
+    assert_send_sync::<crate::msm_job::MsmJob<C>>();
+    assert_send_sync::<crate::terms::Terms<C>>();
+}
+
+#[cfg(feature = "alloc")]
+fn coordinates<C: crate::coordinates::CurveCoordinates>() {
+    assert_send_sync::<crate::coordinates::PointVec<C>>();
+}
@@ -0,0 +1,95 @@
+//! [`proptest`](mod@proptest) strategies for scalars and group elements, available with the `proptest`
+//! feature flag.
+//!
+//! Uniformly random inputs almost never hit the corner cases where group arithmetic bugs
+//! live: the identity, a point added to itself or its negation, scalars near zero or
+//! near the group order. Alongside uniform strategies, this module provides strategies
+//! biased towards those edge cases:
+//!
+//! ```ignore
+//! use group::strategies::{point_edge_biased, scalar_edge_biased};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn mul_distributes(p in point_edge_biased::<G1Projective>(), s in scalar_edge_biased()) {
+//!         prop_assert_eq!((p + p) * s, p * s + p * s);
+//!     }
+//! }
+//! ```
+
+use alloc::vec::Vec;
+
+use ff::PrimeField;
+use proptest::prelude::*;
+
+use crate::Group;
+
+/// Reduces a 512-bit little-endian integer into the field, with negligible bias for
+/// fields of up to 256 bits.
+fn from_limbs<F: PrimeField>(limbs: [u64; 8]) -> F {
+    let two_64 = F::from(u64::MAX) + F::ONE;
+    limbs
+        .iter()
+        .rev()
+        .fold(F::ZERO, |acc, limb| acc * two_64 + F::from(*limb))
+}
+
+/// Returns a strategy for uniformly random scalars.
+pub fn scalar<F: PrimeField>() -> BoxedStrategy<F> {
+    any::<[u64; 8]>().prop_map(from_limbs).boxed()
+}
+
+/// Returns a strategy for scalars that is biased towards edge cases.
+///
+/// Half of the generated scalars are one of `0`, `1`, `2`, `-1` (the order minus one),
+/// `-2`, or a power of two `2^k` for `k < NUM_BITS`; the rest are uniformly random.
+pub fn scalar_edge_biased<F: PrimeField>() -> BoxedStrategy<F> {
+    let special = prop_oneof![
+        Just(F::ZERO),
+        Just(F::ONE),
+        Just(F::ONE.double()),
+        Just(-F::ONE),
+        Just(-F::ONE.double()),
+        (0..F::NUM_BITS).prop_map(|k| (0..k).fold(F::ONE, |acc, _| acc.double())),
+    ];
+    prop_oneof![special, scalar()].boxed()
+}
+
+/// Returns a strategy for uniformly random elements of the subgroup generated by
+/// [`GroupCore::generator`](crate::GroupCore::generator), including the identity.
+pub fn point<G: Group>() -> BoxedStrategy<G> {
+    scalar::<G::Scalar>()
+        .prop_map(|s| G::generator() * s)
+        .boxed()
+}
+
+/// Returns a strategy for elements that is biased towards edge cases.
+///
+/// Half of the generated elements are multiples of the generator by the edge-case
+/// scalars of [`scalar_edge_biased`] (which include the identity, the generator and its
+/// negation); the rest are uniformly random.
+pub fn point_edge_biased<G: Group>() -> BoxedStrategy<G> {
+    scalar_edge_biased::<G::Scalar>()
+        .prop_map(|s| G::generator() * s)
+        .boxed()
+}
+
+/// Returns a strategy that adds one of the given small-order elements to the elements
+/// generated by `points`.
+///
+/// The crate cannot find small-order elements of a group generically, so curve crates
+/// supply them (for example the eight points of order dividing 8 on Curve25519).
+/// Combined with [`point_edge_biased`], this exercises subgroup checks, cofactor
+/// clearing and cofactored equations on elements with a torsion component.
+///
+/// This function will panic if `torsion` is empty.
+pub fn with_torsion<G: Group>(
+    points: impl Strategy<Value = G> + 'static,
+    torsion: Vec<G>,
+) -> BoxedStrategy<G> {
+    assert!(!torsion.is_empty());
+    (points, proptest::sample::select(torsion))
+        .prop_map(|(p, t)| p + t)
+        .boxed()
+}
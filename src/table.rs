@@ -0,0 +1,108 @@
+//! Precomputed tables of multiples of a group element.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::Group;
+
+/// A table of the multiples `[1]P, [2]P, ..., [N]P` of a group element `P`, supporting
+/// constant-time lookups of `[i]P` for signed indices `-N <= i <= N`.
+///
+/// This is the building block of fixed-window and signed-digit constant-time scalar
+/// multiplication: every lookup reads every entry of the table, so the memory access
+/// pattern does not depend on the (secret) index. Negative multiples are not stored, but
+/// computed by a conditional negation after the lookup. Algorithms whose digits are
+/// always odd can use [`OddLookupTable`] instead, which stores half as many multiples.
+#[derive(Clone, Copy, Debug)]
+pub struct LookupTable<G: Group, const N: usize> {
+    table: [G; N],
+}
+
+impl<G: Group + ConditionallySelectable, const N: usize> LookupTable<G, N> {
+    /// Precomputes the multiples `[1]P, ..., [N]P` of the given element.
+    ///
+    /// This function will panic if `N > 128`.
+    pub fn new(p: G) -> Self {
+        assert!(N <= 128);
+
+        let mut table = [p; N];
+        for i in 1..N {
+            table[i] = table[i - 1] + p;
+        }
+        trace!(ADDITIONS, N.saturating_sub(1));
+
+        LookupTable { table }
+    }
+
+    /// Returns `[index]P` in constant time.
+    ///
+    /// The result is unspecified (but the lookup is still constant time) if
+    /// `|index| > N`.
+    pub fn select(&self, index: i8) -> G {
+        // Compute the sign and absolute value of the index without branching.
+        let sign = (index as u8) >> 7;
+        let abs = ((index as i16 ^ -(sign as i16)) + sign as i16) as u8;
+
+        let mut t = G::identity();
+        for (j, p) in (1..).zip(self.table.iter()) {
+            t.conditional_assign(p, abs.ct_eq(&j));
+        }
+
+        let neg = -t;
+        t.conditional_assign(&neg, Choice::from(sign));
+        trace!(TABLE_LOOKUPS);
+        t
+    }
+}
+
+/// A table of the odd multiples `[1]P, [3]P, ..., [2N - 1]P` of a group element `P`,
+/// supporting constant-time lookups of `[i]P` for odd signed indices
+/// `-(2N - 1) <= i <= 2N - 1`.
+///
+/// Signed odd-digit recodings (such as the regular recoding of Joye and Tunstall) only
+/// ever look up odd multiples. Negative multiples are computed by a conditional negation
+/// after the lookup, so this table stores half of the multiples of a [`LookupTable`] of
+/// the same range.
+#[derive(Clone, Copy, Debug)]
+pub struct OddLookupTable<G: Group, const N: usize> {
+    table: [G; N],
+}
+
+impl<G: Group + ConditionallySelectable, const N: usize> OddLookupTable<G, N> {
+    /// Precomputes the odd multiples `[1]P, [3]P, ..., [2N - 1]P` of the given element.
+    ///
+    /// This function will panic if `N > 64`.
+    pub fn new(p: G) -> Self {
+        assert!(N <= 64);
+
+        let dbl = p.double();
+        trace!(DOUBLINGS);
+
+        let mut table = [p; N];
+        for i in 1..N {
+            table[i] = table[i - 1] + dbl;
+        }
+        trace!(ADDITIONS, N.saturating_sub(1));
+
+        OddLookupTable { table }
+    }
+
+    /// Returns `[index]P` in constant time.
+    ///
+    /// The result is unspecified (but the lookup is still constant time) if `index` is
+    /// even or `|index| > 2N - 1`.
+    pub fn select(&self, index: i8) -> G {
+        // Compute the sign and absolute value of the index without branching.
+        let sign = (index as u8) >> 7;
+        let abs = ((index as i16 ^ -(sign as i16)) + sign as i16) as u8;
+
+        let mut t = G::identity();
+        for (j, p) in (0..).zip(self.table.iter()) {
+            t.conditional_assign(p, abs.ct_eq(&(2 * j + 1)));
+        }
+
+        let neg = -t;
+        t.conditional_assign(&neg, Choice::from(sign));
+        trace!(TABLE_LOOKUPS);
+        t
+    }
+}
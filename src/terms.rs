@@ -0,0 +1,196 @@
+//! Linear combinations of curve points, as found in verification equations.
+//!
+//! Pairing-free verification equations (for Schnorr signatures, Bulletproofs and the
+//! like) all check that some sum of `s·P` terms is the identity. [`Terms`] collects those
+//! terms, merges repeated points by summing their scalars and drops vanishing terms, so
+//! that the final evaluation only multiplies each distinct point once.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use ff::{Field, PrimeField};
+
+use crate::{
+    prime::{PrimeCurve, PrimeCurveAffine},
+    util::sort_by_encoding_key,
+    wnaf::{wnaf_form, wnaf_table},
+    GroupEncoding,
+};
+
+/// The w-NAF window size used by [`Terms::evaluate`].
+const STRAUS_WINDOW: usize = 4;
+
+/// A single `scalar · point` term of a linear combination.
+pub struct Term<C: PrimeCurve> {
+    /// The scalar that the point is multiplied by.
+    pub scalar: C::Scalar,
+    /// The point.
+    pub point: C::Affine,
+}
+
+impl<C: PrimeCurve> Clone for Term<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: PrimeCurve> Copy for Term<C> {}
+
+impl<C: PrimeCurve> fmt::Debug for Term<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Term")
+            .field("scalar", &self.scalar)
+            .field("point", &self.point)
+            .finish()
+    }
+}
+
+/// A builder for linear combinations `sum(s_i · P_i)` of curve points.
+///
+/// The scalars and points are treated as public: [`Terms::simplify`] and
+/// [`Terms::evaluate`] run in variable time with respect to which points repeat and
+/// which terms vanish.
+///
+/// ```ignore
+/// // Schnorr verification: [s] G - R - [e] X == 0
+/// let mut terms = Terms::<G1Projective>::new();
+/// terms
+///     .add_term(s, G1Affine::generator())
+///     .add_term(-Scalar::one(), r)
+///     .add_term(-e, x);
+/// assert!(bool::from(terms.evaluate().is_identity()));
+/// ```
+pub struct Terms<C: PrimeCurve> {
+    terms: Vec<Term<C>>,
+}
+
+impl<C: PrimeCurve> Clone for Terms<C> {
+    fn clone(&self) -> Self {
+        Terms {
+            terms: self.terms.clone(),
+        }
+    }
+}
+
+impl<C: PrimeCurve> fmt::Debug for Terms<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.terms.iter()).finish()
+    }
+}
+
+impl<C: PrimeCurve> Default for Terms<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: PrimeCurve> Terms<C> {
+    /// Constructs an empty linear combination.
+    pub fn new() -> Self {
+        Terms { terms: Vec::new() }
+    }
+
+    /// Constructs an empty linear combination with space for `capacity` terms.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Terms {
+            terms: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Adds the term `scalar · point`.
+    pub fn add_term(&mut self, scalar: C::Scalar, point: C::Affine) -> &mut Self {
+        self.terms.push(Term { scalar, point });
+        self
+    }
+
+    /// Returns the number of terms currently held.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns true if there are no terms.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns the current terms.
+    pub fn terms(&self) -> &[Term<C>] {
+        &self.terms
+    }
+
+    /// Merges the terms of repeated points by summing their scalars, and then removes
+    /// every term whose scalar is zero or whose point is the identity.
+    ///
+    /// Afterwards, the terms are sorted by the encodings of their points.
+    pub fn simplify(&mut self) {
+        let encodings = sort_by_encoding_key(&mut self.terms, |t| t.point.to_bytes());
+
+        let mut merged: Vec<Term<C>> = Vec::with_capacity(self.terms.len());
+        for (i, term) in self.terms.iter().enumerate() {
+            match merged.last_mut() {
+                Some(last) if encodings[i].as_ref() == encodings[i - 1].as_ref() => {
+                    last.scalar += term.scalar;
+                }
+                _ => merged.push(*term),
+            }
+        }
+
+        merged.retain(|t| !bool::from(t.scalar.is_zero() | t.point.is_identity()));
+        self.terms = merged;
+    }
+
+    /// Simplifies the terms as with [`Terms::simplify`], and returns their sum.
+    ///
+    /// The sum is computed as a single multi-scalar multiplication with Straus' method:
+    /// every term is recoded in w-NAF form, and all of the terms share one chain of
+    /// doublings. The chain starts at the most significant non-zero digit of any term, so
+    /// linear combinations with short scalars (such as 128-bit random weights in batch
+    /// verification) only pay for the doublings their scalars need. This runs in variable
+    /// time with respect to the scalars.
+    pub fn evaluate(&mut self) -> C {
+        self.simplify();
+
+        let mut tables = Vec::with_capacity(self.terms.len());
+        let mut wnafs = Vec::with_capacity(self.terms.len());
+        for term in &self.terms {
+            let mut table = Vec::new();
+            wnaf_table(&mut table, term.point.to_curve(), STRAUS_WINDOW);
+            tables.push(table);
+
+            let mut wnaf = Vec::new();
+            wnaf_form(&mut wnaf, term.scalar.to_repr(), STRAUS_WINDOW);
+            wnafs.push(wnaf);
+        }
+
+        // Skip the positions above the most significant non-zero digit of every term.
+        let len = wnafs
+            .iter()
+            .filter_map(|wnaf| wnaf.iter().rposition(|&n| n != 0))
+            .max()
+            .map_or(0, |top| top + 1);
+        let mut acc = C::identity();
+        for i in (0..len).rev() {
+            acc = acc.double();
+            trace!(DOUBLINGS);
+
+            for (table, wnaf) in tables.iter().zip(wnafs.iter()) {
+                match wnaf.get(i) {
+                    Some(&n) if n > 0 => acc += table[(n / 2) as usize],
+                    Some(&n) if n < 0 => acc -= table[((-n) / 2) as usize],
+                    _ => continue,
+                }
+                trace!(ADDITIONS);
+            }
+        }
+        acc
+    }
+}
+
+impl<C: PrimeCurve> Extend<(C::Scalar, C::Affine)> for Terms<C> {
+    fn extend<I: IntoIterator<Item = (C::Scalar, C::Affine)>>(&mut self, iter: I) {
+        self.terms.extend(
+            iter.into_iter()
+                .map(|(scalar, point)| Term { scalar, point }),
+        );
+    }
+}
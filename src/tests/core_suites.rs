@@ -0,0 +1,347 @@
+//! Test suites that do not require `alloc`, for testing curve implementations on
+//! targets without a heap.
+
+use core::ops::{Add, Mul, Neg, Sub};
+use ff::{Field, PrimeField};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use subtle::ConstantTimeEq;
+
+use crate::{
+    addition_chain::AdditionChain,
+    prime::{PrimeCurve, PrimeCurveAffine},
+    ConstIdentity, DefaultIsIdentity, Group, GroupEncoding,
+};
+
+/// Tests the group laws, identity handling and compressed encoding of a curve, without
+/// allocating.
+pub fn core_curve_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // Negation edge case with identity.
+    {
+        let z = G::identity().neg();
+        assert!(bool::from(z.is_identity()));
+    }
+
+    // Doubling edge case with identity.
+    {
+        let z = G::identity().double();
+        assert!(bool::from(z.is_identity()));
+    }
+
+    // Equality edge cases with identity
+    {
+        let r = G::random(&mut rng);
+        assert!(G::identity().eq_vartime(&G::identity()));
+        assert!(!G::identity().eq_vartime(&r));
+        assert!(!r.eq_vartime(&G::identity()));
+        assert!(r.eq_vartime(&(r + G::identity())));
+    }
+
+    // Addition edge cases with identity
+    {
+        let mut r = G::random(&mut rng);
+        let rcopy = r;
+        r.add_assign(&G::identity());
+        assert_eq!(r, rcopy);
+        r.add_assign(&G::Affine::identity());
+        assert_eq!(r, rcopy);
+
+        let mut z = G::identity();
+        z.add_assign(&G::identity());
+        assert!(bool::from(z.is_identity()));
+        z.add_assign(&G::Affine::identity());
+        assert!(bool::from(z.is_identity()));
+
+        let mut z2 = z;
+        z2.add_assign(&r);
+
+        z.add_assign(&r.to_affine());
+
+        assert_eq!(z, z2);
+        assert_eq!(z, r);
+    }
+
+    // Transformations
+    {
+        let a = G::random(&mut rng);
+        let b = a.to_affine().to_curve();
+        let c = a.to_affine().to_curve().to_affine().to_curve();
+        assert_eq!(a, b);
+        assert_eq!(b, c);
+    }
+
+    random_addition_tests::<G>();
+    random_multiplication_tests::<G>();
+    random_doubling_tests::<G>();
+    random_negation_tests::<G>();
+    random_compressed_encoding_tests::<G>();
+}
+
+/// Tests that the addition chain `A` multiplies elements of `G` by
+/// [`AdditionChain::constant`].
+pub fn addition_chain_tests<G: Group, A: AdditionChain>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let k = G::Scalar::from_u128(A::constant());
+
+    assert!(bool::from(A::mul(&G::identity()).is_identity()));
+    assert_eq!(A::mul(&G::generator()), G::generator() * k);
+
+    for _ in 0..100 {
+        let p = G::random(&mut rng);
+        assert_eq!(A::mul(&p), p * k);
+    }
+}
+
+/// Tests that `G::default()` is the identity, as promised by [`DefaultIsIdentity`].
+pub fn default_is_identity_tests<G: DefaultIsIdentity>() {
+    assert_eq!(G::default(), G::identity());
+    assert!(bool::from(G::default().is_identity()));
+    assert_eq!(G::generator() + G::default(), G::generator());
+}
+
+/// Tests that [`ConstIdentity::IDENTITY`] is the identity.
+pub fn const_identity_tests<G: ConstIdentity>() {
+    assert_eq!(G::IDENTITY, G::identity());
+    assert!(bool::from(G::IDENTITY.is_identity()));
+    assert_eq!(G::generator() + G::IDENTITY, G::generator());
+}
+
+fn random_negation_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let r = G::random(&mut rng);
+
+        let s = G::Scalar::random(&mut rng);
+        let sneg = s.neg();
+
+        let mut t1 = r;
+        t1.mul_assign(s);
+
+        let mut t2 = r;
+        t2.mul_assign(sneg);
+
+        let mut t3 = t1;
+        t3.add_assign(&t2);
+        assert!(bool::from(t3.is_identity()));
+
+        let mut t4 = t1;
+        t4.add_assign(&t2.to_affine());
+        assert!(bool::from(t4.is_identity()));
+
+        assert_eq!(t1.neg(), t2);
+    }
+}
+
+fn random_doubling_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let mut a = G::random(&mut rng);
+        let mut b = G::random(&mut rng);
+
+        // 2(a + b)
+        let tmp1 = (a + b).double();
+
+        // 2a + 2b
+        a = a.double();
+        b = b.double();
+
+        let mut tmp2 = a;
+        tmp2.add_assign(&b);
+
+        let mut tmp3 = a;
+        tmp3.add_assign(&b.to_affine());
+
+        assert_eq!(tmp1, tmp2);
+        assert_eq!(tmp1, tmp3);
+    }
+}
+
+fn random_multiplication_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let mut a = G::random(&mut rng);
+        let mut b = G::random(&mut rng);
+        let a_affine = a.to_affine();
+        let b_affine = b.to_affine();
+
+        let s = G::Scalar::random(&mut rng);
+
+        // s ( a + b )
+        let mut tmp1 = a;
+        tmp1.add_assign(&b);
+        tmp1.mul_assign(s);
+
+        // sa + sb
+        a.mul_assign(s);
+        b.mul_assign(s);
+
+        let mut tmp2 = a;
+        tmp2.add_assign(&b);
+
+        // Affine multiplication
+        let mut tmp3 = Mul::<G::Scalar>::mul(a_affine, s);
+        tmp3.add_assign(Mul::<G::Scalar>::mul(b_affine, s));
+
+        assert_eq!(tmp1, tmp2);
+        assert_eq!(tmp1, tmp3);
+    }
+}
+
+fn random_addition_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let a = G::random(&mut rng);
+        let b = G::random(&mut rng);
+        let c = G::random(&mut rng);
+        let a_affine = a.to_affine();
+        let b_affine = b.to_affine();
+        let c_affine = c.to_affine();
+
+        // a + a should equal the doubling
+        {
+            let mut aplusa = a;
+            aplusa.add_assign(&a);
+
+            let mut aplusamixed = a;
+            aplusamixed.add_assign(&a.to_affine());
+
+            let adouble = a.double();
+
+            assert_eq!(aplusa, adouble);
+            assert_eq!(aplusa, aplusamixed);
+        }
+
+        let mut tmp = [G::identity(); 6];
+
+        // (a + b) + c
+        tmp[0] = a;
+        tmp[0].add_assign(&b);
+        tmp[0].add_assign(&c);
+
+        // a + (b + c)
+        tmp[1] = b;
+        tmp[1].add_assign(&c);
+        tmp[1].add_assign(&a);
+
+        // (a + c) + b
+        tmp[2] = a;
+        tmp[2].add_assign(&c);
+        tmp[2].add_assign(&b);
+
+        // Mixed addition
+
+        // (a + b) + c
+        tmp[3] = a_affine.to_curve();
+        tmp[3].add_assign(&b_affine);
+        tmp[3].add_assign(&c_affine);
+
+        // a + (b + c)
+        tmp[4] = b_affine.to_curve();
+        tmp[4].add_assign(&c_affine);
+        tmp[4].add_assign(&a_affine);
+
+        // (a + c) + b
+        tmp[5] = a_affine.to_curve();
+        tmp[5].add_assign(&c_affine);
+        tmp[5].add_assign(&b_affine);
+
+        // Affine addition and subtraction
+        {
+            let apb = a + b;
+            let amb = a - b;
+            assert_eq!(a_affine + b_affine, apb);
+            assert_eq!(Add::<&G::Affine>::add(a_affine, &b_affine), apb);
+            assert_eq!(a_affine + b, apb);
+            assert_eq!(Add::<&G>::add(a_affine, &b), apb);
+            assert_eq!(a_affine - b_affine, amb);
+            assert_eq!(Sub::<&G::Affine>::sub(a_affine, &b_affine), amb);
+            assert_eq!(a_affine - b, amb);
+            assert_eq!(Sub::<&G>::sub(a_affine, &b), amb);
+            assert!(bool::from((a_affine - a).is_identity()));
+        }
+
+        // Comparisons
+        for i in 0..6 {
+            for j in 0..6 {
+                assert_eq!(tmp[i], tmp[j]);
+                assert_eq!(tmp[i].to_affine(), tmp[j].to_affine());
+                assert!(bool::from(tmp[i].ct_eq(&tmp[j])));
+                assert!(tmp[i].eq_vartime(&tmp[j]));
+                assert!(bool::from(tmp[i].to_affine().ct_eq(&tmp[j].to_affine())));
+            }
+
+            assert!(!bool::from(tmp[i].ct_eq(&a)));
+            assert!(!tmp[i].eq_vartime(&a));
+            assert!(!bool::from(tmp[i].to_affine().ct_eq(&a_affine)));
+
+            assert!(tmp[i] != a);
+            assert!(tmp[i] != b);
+            assert!(tmp[i] != c);
+
+            assert!(a != tmp[i]);
+            assert!(b != tmp[i]);
+            assert!(c != tmp[i]);
+        }
+    }
+}
+
+fn random_compressed_encoding_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(
+        G::Affine::from_bytes(&G::Affine::identity().to_bytes()).unwrap(),
+        G::Affine::identity()
+    );
+
+    // Slices of the wrong length are rejected without panicking.
+    let compressed = G::Affine::generator().to_bytes();
+    let bytes = compressed.as_ref();
+    assert_eq!(G::Affine::repr_from_slice(bytes).unwrap().as_ref(), bytes);
+    assert!(G::Affine::repr_from_slice(&bytes[1..]).is_none());
+    let mut long = [0; 256];
+    long[..bytes.len()].copy_from_slice(bytes);
+    assert!(G::Affine::repr_from_slice(&long[..bytes.len() + 1]).is_none());
+
+    for _ in 0..1000 {
+        let mut r = G::random(&mut rng).to_affine();
+
+        let compressed = r.to_bytes();
+        let de_compressed = G::Affine::from_bytes(&compressed).unwrap();
+        assert_eq!(de_compressed, r);
+        assert_eq!(G::Affine::from_bytes_on_curve_only(&compressed).unwrap(), r);
+
+        r = r.neg();
+
+        let compressed = r.to_bytes();
+        let de_compressed = G::Affine::from_bytes(&compressed).unwrap();
+        assert_eq!(de_compressed, r);
+    }
+}
@@ -0,0 +1,258 @@
+//! Macros for instantiating the test suites as `#[test]` functions.
+
+/// Instantiates the test suites in [`group::tests`](crate::tests) for a curve as
+/// `#[test]` functions.
+///
+/// The suites that apply to every [`PrimeCurve`](crate::prime::PrimeCurve) are always
+/// instantiated, so curve crates pick up new generic suites without changes. Suites that
+/// need further traits are opted into by name after a semicolon:
+///
+/// | Name | Suites | Requires |
+/// |---|---|---|
+/// | `wnaf` | `random_wnaf_tests` | `WnafGroup` |
+/// | `uncompressed` | `random_uncompressed_encoding_tests` | `Affine: UncompressedEncoding + ConditionallySelectable` |
+/// | `x_only` | `random_x_only_encoding_tests`, `compressed_batch_tests` | `Affine: XOnly` |
+/// | `u_coordinate` | `random_u_coordinate_encoding_tests` | `Affine: UCoordinateEncoding` |
+/// | `affine_coordinates` | `affine_coordinates_tests` | `Affine: AffineCoordinates` |
+/// | `jacobian` | `jacobian_coordinates_tests`, `point_vec_tests` | `JacobianCoordinates` |
+/// | `batch_normalize_jacobian` | `batch_normalize_jacobian_tests` | `JacobianCoordinates`, `Affine: AffineCoordinates + ConditionallySelectable` |
+/// | `short_weierstrass` | `curve_constants_tests` | `Affine: ShortWeierstrass` |
+/// | `constant_time` | `lookup_table_tests`, `ladder_tests`, `blinded_decoding_tests` | `ConditionallySelectable` |
+/// | `cofactor` | `batch_clear_cofactor_tests`, `validation_report_tests` | `CofactorGroup` |
+/// | `psi` | `psi_tests` | `Psi` |
+/// | `bls12_g2` | `bls12_g2_tests` | `Bls12G2` |
+/// | `chain` | `chain_tests` | `Affine: Chain` |
+/// | `elligator` | `elligator_tests` | `Elligator` |
+/// | `tagged` | `tagged_encoding_tests` | `TaggedEncoding` |
+/// | `msm_job` | `msm_job_tests` | `Affine: TaggedEncoding + UncompressedEncoding` |
+/// | `keypair` | `keypair_tests` | `Scalar: FromUniformBytes<64>` |
+/// | `cofactor_inverse` | `cofactor_inverse_tests`, `verify_equation_tests` | `CofactorInverse` |
+/// | `subgroup_hint` | `subgroup_hint_tests` | `SubgroupHint + ConditionallySelectable + Default` |
+/// | `default_identity` | `default_is_identity_tests` | `DefaultIsIdentity` |
+/// | `const_identity` | `const_identity_tests` | `ConstIdentity` |
+///
+/// The generated functions are named after their suites, so the macro should be invoked
+/// in a module of its own:
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod g1_tests {
+///     group::curve_tests!(crate::G1Projective; wnaf, uncompressed, cofactor);
+/// }
+/// ```
+#[macro_export]
+macro_rules! curve_tests {
+    ($curve:ty) => {
+        $crate::curve_tests!($curve;);
+    };
+    ($curve:ty; $($suite:ident),* $(,)?) => {
+        #[test]
+        fn curve_tests() {
+            $crate::tests::curve_tests::<$curve>();
+        }
+
+        #[test]
+        fn accumulator_tests() {
+            $crate::tests::accumulator_tests::<$curve>();
+        }
+
+        #[test]
+        fn compressed_points_tests() {
+            $crate::tests::compressed_points_tests::<$curve>();
+        }
+
+        #[test]
+        fn distribution_tests() {
+            $crate::tests::distribution_tests::<$curve>();
+        }
+
+        #[test]
+        fn encoding_sort_tests() {
+            $crate::tests::encoding_sort_tests::<$curve>();
+        }
+
+        #[test]
+        fn non_identity_tests() {
+            $crate::tests::non_identity_tests::<$curve>();
+        }
+
+        #[test]
+        fn pedersen_commitment_tests() {
+            $crate::tests::pedersen_commitment_tests::<$curve>();
+        }
+
+        #[test]
+        fn terms_tests() {
+            $crate::tests::terms_tests::<$curve>();
+        }
+
+        #[test]
+        fn transcript_tests() {
+            $crate::tests::transcript_tests::<$curve>();
+        }
+
+        $($crate::__curve_tests_suite!($suite, $curve);)*
+    };
+}
+
+/// Instantiates one of the optional suites of [`curve_tests!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __curve_tests_suite {
+    (wnaf, $curve:ty) => {
+        #[test]
+        fn random_wnaf_tests() {
+            $crate::tests::random_wnaf_tests::<$curve>();
+        }
+    };
+    (uncompressed, $curve:ty) => {
+        #[test]
+        fn random_uncompressed_encoding_tests() {
+            $crate::tests::random_uncompressed_encoding_tests::<$curve>();
+        }
+    };
+    (x_only, $curve:ty) => {
+        #[test]
+        fn random_x_only_encoding_tests() {
+            $crate::tests::random_x_only_encoding_tests::<$curve>();
+        }
+
+        #[test]
+        fn compressed_batch_tests() {
+            $crate::tests::compressed_batch_tests::<$curve>();
+        }
+    };
+    (u_coordinate, $curve:ty) => {
+        #[test]
+        fn random_u_coordinate_encoding_tests() {
+            $crate::tests::random_u_coordinate_encoding_tests::<$curve>();
+        }
+    };
+    (affine_coordinates, $curve:ty) => {
+        #[test]
+        fn affine_coordinates_tests() {
+            $crate::tests::affine_coordinates_tests::<$curve>();
+        }
+    };
+    (jacobian, $curve:ty) => {
+        #[test]
+        fn jacobian_coordinates_tests() {
+            $crate::tests::jacobian_coordinates_tests::<$curve>();
+        }
+
+        #[test]
+        fn point_vec_tests() {
+            $crate::tests::point_vec_tests::<$curve>();
+        }
+    };
+    (batch_normalize_jacobian, $curve:ty) => {
+        #[test]
+        fn batch_normalize_jacobian_tests() {
+            $crate::tests::batch_normalize_jacobian_tests::<$curve>();
+        }
+    };
+    (short_weierstrass, $curve:ty) => {
+        #[test]
+        fn curve_constants_tests() {
+            $crate::tests::curve_constants_tests::<$curve>();
+        }
+    };
+    (constant_time, $curve:ty) => {
+        #[test]
+        fn lookup_table_tests() {
+            $crate::tests::lookup_table_tests::<$curve>();
+        }
+
+        #[test]
+        fn ladder_tests() {
+            $crate::tests::ladder_tests::<$curve>();
+        }
+
+        #[test]
+        fn blinded_decoding_tests() {
+            $crate::tests::blinded_decoding_tests::<$curve>();
+        }
+    };
+    (cofactor, $curve:ty) => {
+        #[test]
+        fn batch_clear_cofactor_tests() {
+            $crate::tests::batch_clear_cofactor_tests::<$curve>();
+        }
+
+        #[test]
+        fn validation_report_tests() {
+            $crate::tests::validation_report_tests::<$curve>();
+        }
+    };
+    (psi, $curve:ty) => {
+        #[test]
+        fn psi_tests() {
+            $crate::tests::psi_tests::<$curve>();
+        }
+    };
+    (bls12_g2, $curve:ty) => {
+        #[test]
+        fn bls12_g2_tests() {
+            $crate::tests::bls12_g2_tests::<$curve>();
+        }
+    };
+    (chain, $curve:ty) => {
+        #[test]
+        fn chain_tests() {
+            $crate::tests::chain_tests::<$curve>();
+        }
+    };
+    (elligator, $curve:ty) => {
+        #[test]
+        fn elligator_tests() {
+            $crate::tests::elligator_tests::<$curve>();
+        }
+    };
+    (tagged, $curve:ty) => {
+        #[test]
+        fn tagged_encoding_tests() {
+            $crate::tests::tagged_encoding_tests::<$curve>();
+        }
+    };
+    (msm_job, $curve:ty) => {
+        #[test]
+        fn msm_job_tests() {
+            $crate::tests::msm_job_tests::<$curve>();
+        }
+    };
+    (keypair, $curve:ty) => {
+        #[test]
+        fn keypair_tests() {
+            $crate::tests::keypair_tests::<$curve>();
+        }
+    };
+    (cofactor_inverse, $curve:ty) => {
+        #[test]
+        fn cofactor_inverse_tests() {
+            $crate::tests::cofactor_inverse_tests::<$curve>();
+        }
+
+        #[test]
+        fn verify_equation_tests() {
+            $crate::tests::verify_equation_tests::<$curve>();
+        }
+    };
+    (subgroup_hint, $curve:ty) => {
+        #[test]
+        fn subgroup_hint_tests() {
+            $crate::tests::subgroup_hint_tests::<$curve>();
+        }
+    };
+    (default_identity, $curve:ty) => {
+        #[test]
+        fn default_is_identity_tests() {
+            $crate::tests::default_is_identity_tests::<$curve>();
+        }
+    };
+    (const_identity, $curve:ty) => {
+        #[test]
+        fn const_identity_tests() {
+            $crate::tests::const_identity_tests::<$curve>();
+        }
+    };
+}
@@ -0,0 +1,2110 @@
+use alloc::vec::Vec;
+use core::ops::Neg;
+use ff::{Field, FromUniformBytes, PrimeField};
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use rand_xorshift::XorShiftRng;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use super::core_curve_tests;
+use crate::{
+    accumulator::Accumulator,
+    chain::{Chain, OuterScalar},
+    cofactor::{verify_equation, CofactorGroup, CofactorInverse, SubgroupHint, VerificationMode},
+    commitment::{commit_vector, PedersenGens},
+    compressed::{CompressedBatch, CompressedPoints},
+    coordinates::{
+        AffineCoordinates, CurveCoordinates, JacobianCoordinates, PointVec, ShortWeierstrass,
+    },
+    distributions::RandomNonIdentity,
+    elligator::Elligator,
+    envelope::{EnvelopeError, TaggedEncoding, HEADER_LEN, VERSION},
+    keypair::Keypair,
+    msm_job::{self, MsmFormat, MsmJob, MsmJobError},
+    mul::{
+        cross_check, double_scalar_mul_vartime, ladder, mul_by_limbs, mul_checked, mul_reference,
+    },
+    non_identity::NonIdentity,
+    prime::{PrimeCurve, PrimeCurveAffine},
+    psi::{Bls12G2, Psi},
+    table::{LookupTable, OddLookupTable},
+    terms::Terms,
+    transcript::{Transcript, TranscriptAppend},
+    util::{batch_invert, ct_eq_all, from_bytes_blinded, sort_by_encoding, sort_dedup_by_encoding},
+    validation::{Validate, ValidationReport},
+    wnaf::{wnaf_window_from_costs, WnafGroup},
+    AffineWnafBase, Group, GroupCore, GroupEncoding, UCoordinateEncoding, UncompressedEncoding,
+    WnafScalar, XOnly,
+};
+
+/// Marks a seeded RNG as a [`CryptoRng`], so that APIs requiring one can be tested
+/// deterministically. This must never be used outside of tests.
+struct TestCryptoRng<R: RngCore>(R);
+
+impl<R: RngCore> RngCore for TestCryptoRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl<R: RngCore> CryptoRng for TestCryptoRng<R> {}
+
+pub fn curve_tests<G: PrimeCurve>() {
+    core_curve_tests::<G>();
+    random_equality_tests::<G>();
+    random_transformation_tests::<G>();
+    cost_model_tests::<G>();
+    random_affine_wnaf_tests::<G>();
+    batch_consistency_tests::<G>();
+}
+
+/// Tests that the batch APIs of a curve agree with their element-wise counterparts,
+/// including on empty and length-1 batches.
+///
+/// See [`batch_clear_cofactor_tests`] for the batch APIs of [`CofactorGroup`].
+pub fn batch_consistency_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for len in [0, 1, 2, 7, 32].iter() {
+        let mut points = G::random_batch(&mut rng, *len);
+        let mut scalars = (0..*len)
+            .map(|_| G::Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+        if *len > 0 {
+            points[len / 2] = G::identity();
+            scalars[len / 2] = G::Scalar::ZERO;
+        }
+
+        // batch_normalize and batch_normalize_vec
+        let expected_affine = points.iter().map(|p| p.to_affine()).collect::<Vec<_>>();
+        let mut affine = vec![G::Affine::identity(); *len];
+        G::batch_normalize(&points, &mut affine);
+        assert_eq!(affine, expected_affine);
+        assert_eq!(G::batch_normalize_vec(&points), expected_affine);
+
+        // batch_to_curve and batch_to_curve_vec
+        let mut curve = vec![G::identity(); *len];
+        G::batch_to_curve(&affine, &mut curve);
+        assert_eq!(curve, points);
+        assert_eq!(G::batch_to_curve_vec(&affine), points);
+
+        // batch_invert
+        let expected_inverses = scalars
+            .iter()
+            .map(|s| s.invert().unwrap_or(G::Scalar::ZERO))
+            .collect::<Vec<_>>();
+        let mut inverses = scalars.clone();
+        batch_invert(&mut inverses);
+        assert_eq!(inverses, expected_inverses);
+
+        // ct_eq_all
+        assert!(bool::from(ct_eq_all(&points, &curve)));
+
+        // Multi-scalar multiplication
+        let expected_sum = scalars
+            .iter()
+            .zip(points.iter())
+            .fold(G::identity(), |acc, (s, p)| acc + *p * s);
+        let mut terms = Terms::<G>::new();
+        terms.extend(scalars.iter().copied().zip(affine.iter().copied()));
+        assert_eq!(terms.evaluate(), expected_sum);
+    }
+}
+
+pub fn random_wnaf_tests<G: WnafGroup>() {
+    use crate::wnaf::*;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    {
+        let mut table = vec![];
+        let mut wnaf = vec![];
+
+        for w in 2..14 {
+            for _ in 0..100 {
+                let g = G::random(&mut rng);
+                let s = G::Scalar::random(&mut rng);
+                let mut g1 = g;
+                g1.mul_assign(s);
+
+                wnaf_table(&mut table, g, w);
+                wnaf_form(&mut wnaf, s.to_repr(), w);
+                let g2 = wnaf_exp(&table, &wnaf);
+
+                assert_eq!(g1, g2);
+            }
+        }
+    }
+
+    {
+        fn only_compiles_if_send<S: Send>(_: &S) {}
+
+        for _ in 0..100 {
+            let g = G::random(&mut rng);
+            let s = G::Scalar::random(&mut rng);
+            let mut g1 = g;
+            g1.mul_assign(s);
+
+            let g2 = {
+                let mut wnaf = Wnaf::new();
+                wnaf.base(g, 1).scalar(&s)
+            };
+            let g3 = {
+                let mut wnaf = Wnaf::new();
+                wnaf.scalar(&s).base(g)
+            };
+            let g4 = {
+                let mut wnaf = Wnaf::new();
+                let mut shared = wnaf.base(g, 1).shared();
+
+                only_compiles_if_send(&shared);
+
+                shared.scalar(&s)
+            };
+            let g5 = {
+                let mut wnaf = Wnaf::new();
+                let mut shared = wnaf.scalar(&s).shared();
+
+                only_compiles_if_send(&shared);
+
+                shared.base(g)
+            };
+
+            let g6 = {
+                let mut wnaf = Wnaf::new();
+                {
+                    // Populate the vectors.
+                    wnaf.base(G::random(&mut rng), 1)
+                        .scalar(&G::Scalar::random(&mut rng));
+                }
+                wnaf.base(g, 1).scalar(&s)
+            };
+            let g7 = {
+                let mut wnaf = Wnaf::new();
+                {
+                    // Populate the vectors.
+                    wnaf.base(G::random(&mut rng), 1)
+                        .scalar(&G::Scalar::random(&mut rng));
+                }
+                wnaf.scalar(&s).base(g)
+            };
+            let g8 = {
+                let mut wnaf = Wnaf::new();
+                {
+                    // Populate the vectors.
+                    wnaf.base(G::random(&mut rng), 1)
+                        .scalar(&G::Scalar::random(&mut rng));
+                }
+                let mut shared = wnaf.base(g, 1).shared();
+
+                only_compiles_if_send(&shared);
+
+                shared.scalar(&s)
+            };
+            let g9 = {
+                let mut wnaf = Wnaf::new();
+                {
+                    // Populate the vectors.
+                    wnaf.base(G::random(&mut rng), 1)
+                        .scalar(&G::Scalar::random(&mut rng));
+                }
+                let mut shared = wnaf.scalar(&s).shared();
+
+                only_compiles_if_send(&shared);
+
+                shared.base(g)
+            };
+
+            assert_eq!(g1, g2);
+            assert_eq!(g1, g3);
+            assert_eq!(g1, g4);
+            assert_eq!(g1, g5);
+            assert_eq!(g1, g6);
+            assert_eq!(g1, g7);
+            assert_eq!(g1, g8);
+            assert_eq!(g1, g9);
+        }
+    }
+
+    // A single recoded scalar can be reused against many bases.
+    {
+        let s = G::Scalar::random(&mut rng);
+        let bases = (0..10).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+
+        let mut wnaf = Wnaf::new();
+        let mut wnaf_s = wnaf.scalar(&s);
+        let wnaf_scalar = WnafScalar::<G::Scalar, 4>::new(&s);
+
+        for &g in &bases {
+            let expected = g * s;
+            assert_eq!(wnaf_s.base(g), expected);
+            assert_eq!(wnaf_scalar.base(g), expected);
+            assert_eq!(&WnafBase::<G, 4>::new(g) * &wnaf_scalar, expected);
+        }
+    }
+
+    // A single precomputed base can be shared by reference.
+    {
+        fn only_compiles_if_send_sync<S: Send + Sync>(_: &S) {}
+
+        let g = G::random(&mut rng);
+        let wnaf_base = WnafBase::<G, 4>::new(g);
+        only_compiles_if_send_sync(&wnaf_base);
+        only_compiles_if_send_sync(&&wnaf_base);
+
+        for _ in 0..10 {
+            let s = G::Scalar::random(&mut rng);
+            assert_eq!(wnaf_base.scalar(&s), g * s);
+        }
+    }
+}
+
+fn random_affine_wnaf_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..10 {
+        let g = G::random(&mut rng);
+        let base4 = AffineWnafBase::<G, 4>::new(g);
+        let base7 = AffineWnafBase::<G, 7>::new(g);
+
+        for _ in 0..10 {
+            let s = G::Scalar::random(&mut rng);
+            let expected = g * s;
+            assert_eq!(base4.scalar(&s), expected);
+            assert_eq!(base7.scalar(&s), expected);
+            assert_eq!(&base4 * &WnafScalar::new(&s), expected);
+            assert_eq!(&base7 * &WnafScalar::new(&s), expected);
+        }
+
+        assert!(bool::from(base4.scalar(&G::Scalar::ZERO).is_identity()));
+    }
+}
+
+fn cost_model_tests<G: PrimeCurve>() {
+    assert!(G::ADD_COST > 0);
+    assert!(G::MIXED_ADD_COST > 0);
+    assert!(G::DOUBLE_COST > 0);
+    assert!(G::MIXED_ADD_COST <= G::ADD_COST);
+
+    // More scalars amortize larger tables.
+    let mut prev = 2;
+    for num_scalars in [0, 1, 2, 10, 100, 1000, 1 << 20, 1 << 30].iter() {
+        let window = wnaf_window_from_costs::<G>(*num_scalars);
+        assert!((2..=22).contains(&window));
+        assert!(window >= prev);
+        prev = window;
+    }
+}
+
+fn random_equality_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(bool::from(ct_eq_all::<G>(&[], &[])));
+
+    for _ in 0..100 {
+        let a = (0..10).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+        let mut b = a
+            .iter()
+            .map(|p| p.to_affine().to_curve())
+            .collect::<Vec<_>>();
+        assert!(bool::from(ct_eq_all(&a, &b)));
+        assert!(!bool::from(ct_eq_all(&a, &b[..9])));
+
+        b[9] = b[9].double();
+        assert!(!bool::from(ct_eq_all(&a, &b)));
+    }
+}
+
+fn random_transformation_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let g = G::random(&mut rng);
+        let g_affine = g.to_affine();
+        let g_projective = g_affine.to_curve();
+        assert_eq!(g, g_projective);
+    }
+
+    // Batch normalization
+    for _ in 0..10 {
+        let mut v = (0..1000).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+
+        use rand::distributions::{Distribution, Uniform};
+        let between = Uniform::new(0, 1000);
+        // Sprinkle in some normalized points
+        for _ in 0..5 {
+            v[between.sample(&mut rng)] = G::identity();
+        }
+        for _ in 0..5 {
+            let s = between.sample(&mut rng);
+            v[s] = v[s].to_affine().to_curve();
+        }
+
+        let expected_v = v.iter().map(|v| v.to_affine()).collect::<Vec<_>>();
+
+        let mut normalized = vec![G::Affine::identity(); v.len()];
+        G::batch_normalize(&v, &mut normalized);
+
+        assert_eq!(normalized, expected_v);
+    }
+
+    // Batch conversion from affine
+    {
+        let mut v = (0..100)
+            .map(|_| G::random(&mut rng).to_affine())
+            .collect::<Vec<_>>();
+        v[10] = G::Affine::identity();
+
+        let expected_v = v.iter().map(|p| p.to_curve()).collect::<Vec<_>>();
+
+        let mut lifted = vec![G::identity(); v.len()];
+        G::batch_to_curve(&v, &mut lifted);
+        assert_eq!(lifted, expected_v);
+        assert_eq!(G::batch_to_curve_vec(&v), expected_v);
+    }
+
+    // Tweaking affine points
+    {
+        let p = G::random(&mut rng).to_affine();
+        let t = G::Scalar::random(&mut rng);
+        assert_eq!(
+            p.tweak_add(&t).unwrap(),
+            (p.to_curve() + G::generator() * t).to_affine()
+        );
+        assert_eq!(p.tweak_add(&G::Scalar::ZERO).unwrap(), p);
+        assert_eq!(
+            G::Affine::identity().tweak_add(&t).unwrap(),
+            (G::generator() * t).to_affine()
+        );
+
+        // The tweak that cancels the point is rejected.
+        let q = G::generator() * t;
+        assert!(bool::from((-q).to_affine().tweak_add(&t).is_none()));
+    }
+
+    // Summing affine points
+    {
+        assert!(bool::from(G::sum_affine(&[]).is_identity()));
+
+        let mut v = (0..100)
+            .map(|_| G::random(&mut rng).to_affine())
+            .collect::<Vec<_>>();
+        v[10] = G::Affine::identity();
+        v[20] = v[30];
+        v[40] = -v[50];
+
+        let expected = v.iter().map(|p| p.to_curve()).sum::<G>();
+        assert_eq!(G::sum_affine(&v), expected);
+    }
+}
+
+pub fn random_uncompressed_encoding_tests<G: PrimeCurve>()
+where
+    <G as PrimeCurve>::Affine: UncompressedEncoding + ConditionallySelectable,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(
+        G::Affine::from_uncompressed(&G::Affine::identity().to_uncompressed()).unwrap(),
+        G::Affine::identity()
+    );
+    assert_eq!(
+        G::Affine::identity()
+            .to_uncompressed_checked()
+            .unwrap()
+            .as_ref(),
+        G::Affine::identity().to_uncompressed().as_ref()
+    );
+
+    // Slices of the wrong length are rejected without panicking.
+    let uncompressed = G::Affine::identity().to_uncompressed();
+    let bytes = uncompressed.as_ref();
+    assert_eq!(
+        G::Affine::uncompressed_from_slice(bytes).unwrap().as_ref(),
+        bytes
+    );
+    assert!(G::Affine::uncompressed_from_slice(&bytes[1..]).is_none());
+    assert!(G::Affine::uncompressed_from_slice(&[bytes, &[0]].concat()).is_none());
+
+    for _ in 0..1000 {
+        let r = G::random(&mut rng).to_affine();
+
+        let uncompressed = r.to_uncompressed();
+        assert_eq!(
+            uncompressed.as_ref().len(),
+            <G::Affine as UncompressedEncoding>::UNCOMPRESSED_SIZE
+        );
+        assert_ne!(
+            uncompressed.as_ref(),
+            G::Affine::identity().to_uncompressed().as_ref()
+        );
+        assert_eq!(
+            r.to_uncompressed_checked().unwrap().as_ref(),
+            uncompressed.as_ref()
+        );
+        let de_uncompressed = G::Affine::from_uncompressed(&uncompressed).unwrap();
+        assert_eq!(de_uncompressed, r);
+        assert_eq!(
+            G::Affine::from_uncompressed_on_curve_only(&uncompressed).unwrap(),
+            r
+        );
+    }
+}
+
+pub fn random_x_only_encoding_tests<G: PrimeCurve>()
+where
+    <G as PrimeCurve>::Affine: XOnly,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let r = G::random(&mut rng).to_affine();
+
+        let x = r.to_x_bytes();
+        assert_eq!(x.as_ref(), r.neg().to_x_bytes().as_ref());
+
+        let de_x = G::Affine::from_x_bytes_even_y(&x).unwrap();
+        assert!(bool::from(de_x.has_even_y()));
+        if bool::from(r.has_even_y()) {
+            assert_eq!(de_x, r);
+        } else {
+            assert_eq!(de_x, r.neg());
+        }
+    }
+
+    // Batch deserialization agrees with deserializing each point.
+    {
+        let xs = (0..100)
+            .map(|_| G::random(&mut rng).to_affine().to_x_bytes())
+            .collect::<Vec<_>>();
+        let batch = G::Affine::batch_from_x_bytes_even_y(&xs);
+        assert_eq!(batch.len(), xs.len());
+        for (x, p) in xs.iter().zip(batch) {
+            assert_eq!(p.unwrap(), G::Affine::from_x_bytes_even_y(x).unwrap());
+        }
+    }
+}
+
+pub fn pedersen_commitment_tests<G: Group>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let gens = PedersenGens::new(G::random(&mut rng), G::random(&mut rng));
+
+    for _ in 0..100 {
+        let a = G::Scalar::random(&mut rng);
+        let b = G::Scalar::random(&mut rng);
+        let r = G::Scalar::random(&mut rng);
+        let s = G::Scalar::random(&mut rng);
+
+        // Commitments are additively homomorphic.
+        assert_eq!(
+            gens.commit(&a, &r) + gens.commit(&b, &s),
+            gens.commit(&(a + b), &(r + s))
+        );
+
+        // A single-element vector commitment matches the scalar commitment.
+        assert_eq!(
+            commit_vector(
+                &[*gens.value_generator()],
+                &[a],
+                gens.blinding_generator(),
+                &r
+            ),
+            gens.commit(&a, &r)
+        );
+    }
+
+    // Vector commitments are the sum of the per-value commitments.
+    let value_gens = (0..10).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+    let values = (0..10)
+        .map(|_| G::Scalar::random(&mut rng))
+        .collect::<Vec<_>>();
+    let r = G::Scalar::random(&mut rng);
+
+    let expected = value_gens
+        .iter()
+        .zip(values.iter())
+        .fold(*gens.blinding_generator() * r, |acc, (g, v)| acc + *g * v);
+    assert_eq!(
+        commit_vector(&value_gens, &values, gens.blinding_generator(), &r),
+        expected
+    );
+}
+
+pub fn random_u_coordinate_encoding_tests<G: PrimeCurve>()
+where
+    <G as PrimeCurve>::Affine: UCoordinateEncoding,
+{
+    use rand::RngCore;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let r = G::random(&mut rng).to_affine();
+
+        let u = r.to_u_bytes();
+        assert_eq!(u.as_ref(), r.neg().to_u_bytes().as_ref());
+
+        let de_u = G::Affine::from_u_bytes(&u).unwrap();
+        assert!(de_u == r || de_u == r.neg());
+    }
+
+    for _ in 0..1000 {
+        let mut scalar = <G::Affine as UCoordinateEncoding>::ScalarRepr::default();
+        rng.fill_bytes(scalar.as_mut());
+
+        G::Affine::clamp(&mut scalar);
+        let clamped = scalar;
+        G::Affine::clamp(&mut scalar);
+        assert_eq!(scalar.as_ref(), clamped.as_ref());
+    }
+}
+
+pub fn compressed_batch_tests<G: PrimeCurve>()
+where
+    <G as PrimeCurve>::Affine: XOnly,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for n in [0, 1, 7, 8, 9, 100] {
+        let points = (0..n)
+            .map(|_| G::random(&mut rng).to_affine())
+            .collect::<Vec<_>>();
+
+        let batch = CompressedBatch::new(&points);
+        assert_eq!(batch.len(), n);
+        assert_eq!(batch.decompress().unwrap(), points);
+
+        let bytes = batch.to_bytes();
+        let x_len = <G::Affine as XOnly>::XRepr::default().as_ref().len();
+        assert_eq!(bytes.len(), n * x_len + (n + 7) / 8);
+
+        let parsed = CompressedBatch::<G::Affine>::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.decompress().unwrap(), points);
+
+        // Truncated and extended encodings are rejected.
+        if !bytes.is_empty() {
+            assert!(CompressedBatch::<G::Affine>::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        }
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(CompressedBatch::<G::Affine>::from_bytes(&extended).is_none());
+
+        // Non-zero padding bits are rejected.
+        if n % 8 != 0 {
+            let mut padded = bytes.clone();
+            *padded.last_mut().unwrap() |= 0x80;
+            assert!(CompressedBatch::<G::Affine>::from_bytes(&padded).is_none());
+        }
+    }
+}
+
+pub fn point_vec_tests<G: PrimeCurve + CurveCoordinates>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // Coordinates round-trip through the unchecked constructor.
+    for _ in 0..100 {
+        let p = G::random(&mut rng);
+        let (x, y, z) = p.coordinates();
+        assert_eq!(G::from_coordinates_unchecked(x, y, z), p);
+    }
+
+    let empty = PointVec::<G>::new();
+    assert!(empty.is_empty());
+    assert!(empty.get(0).is_none());
+    assert!(empty.to_points().is_empty());
+
+    let mut points = (0..100).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+    points.push(G::identity());
+
+    let mut v = PointVec::from_points(&points);
+    assert_eq!(v.len(), points.len());
+    assert_eq!(v.to_points(), points);
+    for (i, p) in points.iter().enumerate() {
+        assert_eq!(v.get(i).unwrap(), *p);
+    }
+    assert!(v.get(points.len()).is_none());
+
+    for (i, p) in points.iter().enumerate() {
+        assert_eq!((v.x()[i], v.y()[i], v.z()[i]), p.coordinates());
+    }
+
+    let p = G::random(&mut rng);
+    v.push(&p);
+    assert_eq!(v.get(points.len()).unwrap(), p);
+}
+
+pub fn compressed_points_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let points = (0..100)
+        .map(|_| G::random(&mut rng).to_affine())
+        .collect::<Vec<_>>();
+
+    let store = CompressedPoints::from_points(&points);
+    assert_eq!(store.len(), points.len());
+    assert!(store.is_validated());
+    for (i, p) in points.iter().enumerate() {
+        assert_eq!(store.get(i).unwrap(), *p);
+    }
+
+    let mut store = CompressedPoints::<G::Affine>::new(store.reprs().to_vec());
+    assert!(!store.is_validated());
+    for (i, p) in points.iter().enumerate() {
+        assert_eq!(store.get(i).unwrap(), *p);
+    }
+
+    let mut out = vec![G::Affine::identity(); 10];
+    assert!(bool::from(store.get_batch(20, &mut out)));
+    assert_eq!(&out[..], &points[20..30]);
+
+    assert!(bool::from(store.validate()));
+    assert!(store.is_validated());
+    assert!(bool::from(store.get_batch(90, &mut out)));
+    assert_eq!(&out[..], &points[90..]);
+}
+
+pub fn accumulator_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let acc = Accumulator::<G>::new();
+    assert!(bool::from(acc.into_inner().is_identity()));
+    assert_eq!(acc, Accumulator::default());
+
+    let points = (0..100).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+    let expected = points.iter().fold(G::identity(), |acc, p| acc + p);
+
+    let mut acc = Accumulator::<G>::new();
+    acc.extend(&points);
+    assert_eq!(acc.into_inner(), expected);
+    assert_eq!(acc.to_affine(), expected.to_affine());
+
+    let mut mixed = Accumulator::<G>::new();
+    for p in &points {
+        mixed.add_affine(&p.to_affine());
+    }
+    assert_eq!(mixed, acc);
+
+    for p in &points {
+        mixed -= p;
+    }
+    assert!(bool::from(mixed.into_inner().is_identity()));
+
+    let mut owned = Accumulator::<G>::new();
+    owned.extend(points.iter().copied());
+    owned += points[0];
+    owned.sub_affine(&points[0].to_affine());
+    assert_eq!(owned, acc);
+}
+
+pub fn distribution_tests<G: Group>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let samples = (&mut rng)
+        .sample_iter(RandomNonIdentity)
+        .take(100)
+        .collect::<Vec<G>>();
+    for (i, p) in samples.iter().enumerate() {
+        assert!(!bool::from(p.is_identity()));
+        for q in &samples[..i] {
+            assert!(p != q);
+        }
+    }
+
+    // Batch sampling
+    assert!(G::random_batch(&mut rng, 0).is_empty());
+    let batch = G::random_batch(&mut rng, 100);
+    let array = G::random_array::<100>(&mut rng);
+    assert_eq!(batch.len(), 100);
+    for (i, p) in batch.iter().chain(array.iter()).enumerate() {
+        assert!(!bool::from(p.is_identity()));
+        for q in batch.iter().chain(array.iter()).take(i) {
+            assert!(p != q);
+        }
+    }
+}
+
+pub fn jacobian_coordinates_tests<G: PrimeCurve + JacobianCoordinates>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let (_, _, z) = G::identity().jacobian_coordinates();
+    assert!(bool::from(z.is_zero()));
+
+    for _ in 0..100 {
+        let p = G::random(&mut rng);
+        let (x, y, z) = p.jacobian_coordinates();
+        assert!(!bool::from(z.is_zero()));
+        assert_eq!(G::new_jacobian(x, y, z).unwrap(), p);
+        assert_eq!(G::new_jacobian_unchecked(x, y, z), p);
+
+        // Rescaling the coordinates does not change the point.
+        let l = G::Base::random(&mut rng);
+        let l2 = l.square();
+        let l3 = l2 * l;
+        assert_eq!(G::new_jacobian(x * l2, y * l3, z * l).unwrap(), p);
+
+        // Perturbing a coordinate takes the point off the curve.
+        assert!(bool::from(
+            G::new_jacobian(x, y + G::Base::ONE, z).is_none()
+        ));
+    }
+
+    // Batch construction
+    {
+        assert!(G::batch_new_jacobian(&[]).unwrap().is_empty());
+
+        let points = (0..100).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+        let mut coords = points
+            .iter()
+            .map(|p| p.jacobian_coordinates())
+            .collect::<Vec<_>>();
+        assert_eq!(G::batch_new_jacobian(&coords).unwrap(), points);
+
+        coords[50].1 += G::Base::ONE;
+        assert!(bool::from(G::batch_new_jacobian(&coords).is_none()));
+    }
+}
+
+pub fn batch_normalize_jacobian_tests<G: PrimeCurve + JacobianCoordinates>()
+where
+    G::Affine: AffineCoordinates<Base = G::Base> + ConditionallySelectable,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for &len in &[0, 1, 2, 100] {
+        let mut points = (0..len).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+        if len > 1 {
+            // Identities anywhere in the batch must not disturb the other elements.
+            points[0] = G::identity();
+            points[len - 1] = G::identity();
+        }
+
+        let mut affine = vec![G::generator().to_affine(); len];
+        G::batch_normalize_jacobian(&points, &mut affine);
+        for (p, a) in points.iter().zip(affine.iter()) {
+            assert_eq!(p.to_affine(), *a);
+        }
+    }
+
+    // Rescaled coordinates normalize to the same affine element.
+    let p = G::random(&mut rng);
+    let (x, y, z) = p.jacobian_coordinates();
+    let l = G::Base::random(&mut rng);
+    let q = G::new_jacobian_unchecked(x * l.square(), y * l.square() * l, z * l);
+    let mut affine = [G::identity().to_affine(); 2];
+    G::batch_normalize_jacobian(&[p, q], &mut affine);
+    assert_eq!(affine[0], p.to_affine());
+    assert_eq!(affine[1], p.to_affine());
+}
+
+pub fn affine_coordinates_tests<G: PrimeCurve>()
+where
+    <G as PrimeCurve>::Affine: AffineCoordinates,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(bool::from(G::Affine::identity().coordinates().is_none()));
+    assert!(!bool::from(G::Affine::identity().y_parity()));
+
+    for _ in 0..100 {
+        let p = G::random(&mut rng).to_affine();
+        let (x, y) = p.coordinates().unwrap();
+        assert_eq!(G::Affine::from_coordinates(x, y).unwrap(), p);
+        assert_eq!(G::Affine::from_coordinates_unchecked(x, y), p);
+
+        // Points whose y-coordinates are negations of each other (such as a point and
+        // its negation, on short Weierstrass curves) have opposite parities.
+        let (_, neg_y) = (-p).coordinates().unwrap();
+        if neg_y == -y {
+            assert_ne!(bool::from(p.y_parity()), bool::from((-p).y_parity()));
+        }
+
+        // Perturbing a coordinate takes the point off the curve.
+        assert!(bool::from(
+            G::Affine::from_coordinates(x, y + <G::Affine as AffineCoordinates>::Base::ONE)
+                .is_none()
+        ));
+    }
+
+    // Batch construction
+    {
+        assert!(G::Affine::batch_from_coordinates(&[]).unwrap().is_empty());
+
+        let points = (0..100)
+            .map(|_| G::random(&mut rng).to_affine())
+            .collect::<Vec<_>>();
+        let mut coords = points
+            .iter()
+            .map(|p| p.coordinates().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(G::Affine::batch_from_coordinates(&coords).unwrap(), points);
+
+        coords[50].1 += <G::Affine as AffineCoordinates>::Base::ONE;
+        assert!(bool::from(
+            G::Affine::batch_from_coordinates(&coords).is_none()
+        ));
+    }
+}
+
+#[cfg(feature = "display")]
+pub fn display_tests<G: PrimeCurve>()
+where
+    <G as PrimeCurve>::Affine: AffineCoordinates,
+{
+    use crate::display::{Hex, Normalized};
+    use alloc::string::String;
+    use core::fmt::Write;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(format!("{:?}", Normalized(&G::identity())), "Identity");
+
+    for _ in 0..100 {
+        let p = G::random(&mut rng);
+
+        let mut expected = String::from("0x");
+        for byte in p.to_bytes().as_ref() {
+            write!(expected, "{:02x}", byte).unwrap();
+        }
+        assert_eq!(format!("{}", Hex(&p)), expected);
+        assert_eq!(format!("{:?}", Hex(&p)), expected);
+        assert_eq!(format!("{}", Hex(&p.to_affine())), expected);
+
+        // Equal points with different internal representations are formatted identically.
+        let q = p.double() - p;
+        let (x, y) = p.to_affine().coordinates().unwrap();
+        let expected = format!("Affine {{ x: {:?}, y: {:?} }}", x, y);
+        assert_eq!(format!("{:?}", Normalized(&p)), expected);
+        assert_eq!(format!("{:?}", Normalized(&q)), expected);
+    }
+}
+
+pub fn psi_tests<G: PrimeCurve + Psi>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(bool::from(G::identity().psi().is_identity()));
+    assert!(bool::from(G::identity().psi2().is_identity()));
+
+    for _ in 0..100 {
+        let a = G::random(&mut rng);
+        let b = G::random(&mut rng);
+        let s = G::Scalar::random(&mut rng);
+
+        // ψ is a group endomorphism.
+        assert_eq!((a + b).psi(), a.psi() + b.psi());
+        assert_eq!((-a).psi(), -a.psi());
+        assert_eq!((a * s).psi(), a.psi() * s);
+        assert!(!bool::from(a.psi().is_identity()));
+
+        assert_eq!(a.psi2(), a.psi().psi());
+    }
+}
+
+pub fn bls12_g2_tests<G: PrimeCurve + Bls12G2>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let x = if G::X_IS_NEGATIVE {
+        -G::Scalar::from(G::X)
+    } else {
+        G::Scalar::from(G::X)
+    };
+    // On the prime-order subgroup, clearing the cofactor multiplies by h(x) = 4x² - 2x - 1.
+    let h = x.square().double().double() - x.double() - G::Scalar::ONE;
+
+    assert!(bool::from(G::identity().is_torsion_free_psi()));
+    assert!(bool::from(G::identity().clear_cofactor_psi().is_identity()));
+
+    for _ in 0..100 {
+        let p = G::random(&mut rng);
+
+        assert_eq!(p.mul_by_x(), p * x);
+        assert!(bool::from(p.is_torsion_free_psi()));
+        assert_eq!(p.clear_cofactor_psi(), p * h);
+    }
+}
+
+pub fn chain_tests<G: PrimeCurve>()
+where
+    <G as PrimeCurve>::Affine: Chain,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    type Base<G> = <<G as PrimeCurve>::Affine as Chain>::InnerBase;
+
+    assert_eq!(Base::<G>::MODULUS, OuterScalar::<G::Affine>::MODULUS);
+    assert!(G::Affine::identity()
+        .coordinates_as_outer_scalars()
+        .is_none());
+
+    for _ in 0..100 {
+        let x = Base::<G>::random(&mut rng);
+        let s = G::Affine::base_to_outer_scalar(&x);
+        assert_eq!(G::Affine::outer_scalar_to_base(&s), x);
+
+        let s = OuterScalar::<G::Affine>::random(&mut rng);
+        let x = G::Affine::outer_scalar_to_base(&s);
+        assert_eq!(G::Affine::base_to_outer_scalar(&x), s);
+
+        // The conversions are ring homomorphisms.
+        let a = Base::<G>::random(&mut rng);
+        let b = Base::<G>::random(&mut rng);
+        assert_eq!(
+            G::Affine::base_to_outer_scalar(&(a * b + a)),
+            G::Affine::base_to_outer_scalar(&a) * G::Affine::base_to_outer_scalar(&b)
+                + G::Affine::base_to_outer_scalar(&a)
+        );
+
+        let p = G::random(&mut rng).to_affine();
+        let (x, y) = p.coordinates().unwrap();
+        let (sx, sy) = p.coordinates_as_outer_scalars().unwrap();
+        assert_eq!(sx, G::Affine::base_to_outer_scalar(&x));
+        assert_eq!(sy, G::Affine::base_to_outer_scalar(&y));
+    }
+}
+
+pub fn lookup_table_tests<G: PrimeCurve + ConditionallySelectable>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    fn check<G: PrimeCurve + ConditionallySelectable, const N: usize>(p: G) {
+        let table = LookupTable::<G, N>::new(p);
+        for i in -(N as i16)..=(N as i16).min(127) {
+            let expected = p * G::Scalar::from(i.unsigned_abs() as u64);
+            let expected = if i < 0 { -expected } else { expected };
+            assert_eq!(table.select(i as i8), expected);
+        }
+    }
+
+    for _ in 0..10 {
+        let p = G::random(&mut rng);
+        check::<G, 0>(p);
+        check::<G, 1>(p);
+        check::<G, 8>(p);
+        check::<G, 16>(p);
+        check::<G, 128>(p);
+    }
+
+    check::<G, 8>(G::identity());
+
+    fn check_odd<G: PrimeCurve + ConditionallySelectable, const N: usize>(p: G) {
+        let table = OddLookupTable::<G, N>::new(p);
+        for i in (-(2 * N as i16 - 1)..=(2 * N as i16 - 1)).step_by(2) {
+            let expected = p * G::Scalar::from(i.unsigned_abs() as u64);
+            let expected = if i < 0 { -expected } else { expected };
+            assert_eq!(table.select(i as i8), expected);
+        }
+    }
+
+    for _ in 0..10 {
+        let p = G::random(&mut rng);
+        check_odd::<G, 1>(p);
+        check_odd::<G, 8>(p);
+        check_odd::<G, 64>(p);
+    }
+
+    check_odd::<G, 8>(G::identity());
+}
+
+pub fn blinded_decoding_tests<G: PrimeCurve + ConditionallySelectable>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = G::random(&mut rng);
+        let (decoded, is_valid) = from_bytes_blinded::<G>(&p.to_bytes());
+        assert!(bool::from(is_valid));
+        assert_eq!(decoded, p);
+    }
+
+    // Find an encoding that does not decode to a point.
+    let mut invalid = G::Repr::default();
+    loop {
+        rng.fill(invalid.as_mut());
+        if bool::from(G::from_bytes(&invalid).is_none()) {
+            break;
+        }
+    }
+    let (decoded, is_valid) = from_bytes_blinded::<G>(&invalid);
+    assert!(!bool::from(is_valid));
+    assert_eq!(decoded, G::generator());
+}
+
+pub fn ladder_tests<G: PrimeCurve + ConditionallySelectable>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = G::random(&mut rng);
+    assert!(bool::from(ladder(&p, &G::Scalar::ZERO).is_identity()));
+    assert_eq!(ladder(&p, &G::Scalar::ONE), p);
+    assert_eq!(ladder(&p, &-G::Scalar::ONE), -p);
+    assert!(bool::from(
+        ladder(&G::identity(), &G::Scalar::random(&mut rng)).is_identity()
+    ));
+
+    for _ in 0..100 {
+        let p = G::random(&mut rng);
+        let s = G::Scalar::random(&mut rng);
+        assert_eq!(ladder(&p, &s), p * s);
+        assert_eq!(mul_checked(&p, &s).unwrap(), p * s);
+        assert_eq!(mul_reference(&p, &s), p * s);
+        assert_eq!(cross_check(&p, &s, p * s), p * s);
+    }
+    assert!(bool::from(
+        mul_reference(&p, &G::Scalar::ZERO).is_identity()
+    ));
+    assert_eq!(mul_reference(&p, &-G::Scalar::ONE), -p);
+
+    // Double-scalar multiplication
+    {
+        let zero = G::Scalar::ZERO;
+        let q = G::random(&mut rng);
+        let s = G::Scalar::random(&mut rng);
+        assert!(bool::from(
+            double_scalar_mul_vartime(&zero, &p, &zero, &q).is_identity()
+        ));
+        assert_eq!(double_scalar_mul_vartime(&s, &p, &zero, &q), p * s);
+        assert_eq!(double_scalar_mul_vartime(&zero, &p, &s, &q), q * s);
+        assert_eq!(double_scalar_mul_vartime(&s, &p, &s, &p), p * (s + s));
+        assert!(bool::from(
+            double_scalar_mul_vartime(&s, &p, &s, &-p).is_identity()
+        ));
+        assert_eq!(double_scalar_mul_vartime(&s, &G::identity(), &s, &q), q * s);
+
+        for _ in 0..100 {
+            let p = G::random(&mut rng);
+            let q = G::random(&mut rng);
+            let a = G::Scalar::random(&mut rng);
+            let b = G::Scalar::random(&mut rng);
+            assert_eq!(double_scalar_mul_vartime(&a, &p, &b, &q), p * a + q * b);
+        }
+    }
+
+    assert!(bool::from(
+        mul_checked(&G::identity(), &G::Scalar::random(&mut rng))
+            .unwrap()
+            .is_identity()
+    ));
+    assert!(bool::from(
+        mul_checked(&p, &G::Scalar::ZERO).unwrap().is_identity()
+    ));
+
+    // Multiplication by unreduced limbs
+    {
+        let two_64 = G::Scalar::from(u64::MAX) + G::Scalar::ONE;
+
+        assert!(bool::from(mul_by_limbs(&p, &[]).is_identity()));
+        assert!(bool::from(mul_by_limbs(&p, &[0, 0]).is_identity()));
+        assert_eq!(mul_by_limbs(&p, &[1]), p);
+
+        for len in 1..9 {
+            let p = G::random(&mut rng);
+            let limbs = (0..len).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+            let s = limbs.iter().rev().fold(G::Scalar::ZERO, |acc, &limb| {
+                acc * two_64 + G::Scalar::from(limb)
+            });
+            assert_eq!(mul_by_limbs(&p, &limbs), p * s);
+        }
+    }
+}
+
+pub fn batch_clear_cofactor_tests<G: CofactorGroup>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(G::batch_clear_cofactor(&[]).is_empty());
+
+    for len in [1, 2, 10, 100].iter() {
+        let mut points = (0..*len).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+        points[len / 2] = G::identity();
+
+        let expected = points
+            .iter()
+            .map(|p| p.clear_cofactor())
+            .collect::<Vec<_>>();
+        let cleared = G::batch_clear_cofactor(&points);
+        assert_eq!(cleared, expected);
+        assert!(bool::from(cleared[len / 2].is_identity()));
+    }
+}
+
+pub fn cofactor_inverse_tests<G: CofactorInverse>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(
+        G::cofactor_multiplier() * G::cofactor_multiplier_inverse(),
+        G::Scalar::ONE
+    );
+
+    for _ in 0..100 {
+        // clear_cofactor multiplies subgroup elements by the multiplier...
+        let s = <G::Subgroup as GroupCore>::random(&mut rng);
+        let p: G = s.into();
+        assert_eq!(p.clear_cofactor(), s * G::cofactor_multiplier());
+
+        // ...which the preserving variant undoes.
+        assert_eq!(G::scale_by_cofactor_inverse(&p.clear_cofactor()), s);
+        assert_eq!(p.clear_cofactor_preserving_subgroup(), s);
+
+        // Arbitrary elements are still mapped into the subgroup.
+        let q = G::random(&mut rng);
+        let cleared: G = q.clear_cofactor_preserving_subgroup().into();
+        assert!(bool::from(cleared.is_torsion_free()));
+
+        // Decomposition splits off the small-order component.
+        let (prime, torsion) = q.decompose();
+        assert_eq!(prime, q.clear_cofactor_preserving_subgroup());
+        assert!(bool::from(torsion.is_small_order()));
+        assert_eq!(torsion + prime, q);
+
+        let (prime, torsion) = p.decompose();
+        assert_eq!(prime, s);
+        assert!(bool::from(torsion.is_identity()));
+    }
+}
+
+pub fn verify_equation_tests<G: CofactorInverse>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let modes = [VerificationMode::Cofactorless, VerificationMode::Cofactored];
+
+    for _ in 0..100 {
+        let b: G = <G::Subgroup as GroupCore>::random(&mut rng).into();
+        let c: G = <G::Subgroup as GroupCore>::random(&mut rng).into();
+        let s = G::Scalar::random(&mut rng);
+        let t = G::Scalar::random(&mut rng);
+        let a = -(b * s + c * t);
+
+        // A valid equation is accepted in both modes, and an invalid one in neither.
+        for mode in modes.iter() {
+            assert!(bool::from(verify_equation(&a, &s, &b, &t, &c, *mode)));
+            assert!(!bool::from(verify_equation(
+                &(a + b),
+                &s,
+                &b,
+                &t,
+                &c,
+                *mode
+            )));
+        }
+
+        // A small-order component is only ignored by cofactored verification.
+        let (_, torsion) = G::random(&mut rng).decompose();
+        if !bool::from(torsion.is_identity()) {
+            let a = a + torsion;
+            assert!(!bool::from(verify_equation(
+                &a,
+                &s,
+                &b,
+                &t,
+                &c,
+                VerificationMode::Cofactorless
+            )));
+            assert!(bool::from(verify_equation(
+                &a,
+                &s,
+                &b,
+                &t,
+                &c,
+                VerificationMode::Cofactored
+            )));
+        }
+    }
+}
+
+pub fn subgroup_hint_tests<G: SubgroupHint + ConditionallySelectable + Default>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p: G = <G::Subgroup as GroupCore>::random(&mut rng).into();
+        let (bytes, hint) = p.to_bytes_with_hint();
+        assert_eq!(bytes.as_ref(), p.to_bytes().as_ref());
+        assert!(bool::from(p.is_torsion_free_with_hint(&hint)));
+        assert_eq!(G::from_bytes_with_hint(&bytes, &hint).unwrap(), p);
+
+        // Elements outside the subgroup are rejected whatever the hint.
+        let q = G::random(&mut rng);
+        if !bool::from(q.is_torsion_free()) {
+            let mut hint = q.subgroup_hint();
+            assert!(!bool::from(q.is_torsion_free_with_hint(&hint)));
+            assert!(bool::from(
+                G::from_bytes_with_hint(&q.to_bytes(), &hint).is_none()
+            ));
+            rng.fill_bytes(hint.as_mut());
+            assert!(!bool::from(q.is_torsion_free_with_hint(&hint)));
+            assert!(!bool::from(
+                q.is_torsion_free_with_hint(&G::Hint::default())
+            ));
+        }
+    }
+}
+
+pub fn validation_report_tests<G: CofactorGroup>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p: G = <G::Subgroup as GroupCore>::random(&mut rng).into();
+        assert!(bool::from(p.is_in_correct_subgroup_assuming_on_curve()));
+        assert!(bool::from(p.is_torsion_free()));
+
+        let report = ValidationReport::new::<G>(&p.to_bytes());
+        assert!(report.is_valid());
+        assert_eq!(p.validate(), report);
+        assert_eq!(format!("{}", report), "the encoding is valid");
+    }
+
+    // Points outside the subgroup are on the curve, but fail the subgroup check.
+    for _ in 0..100 {
+        let p = <G as GroupCore>::random(&mut rng);
+        if bool::from(p.is_torsion_free()) {
+            continue;
+        }
+        let report = p.validate();
+        assert!(!report.is_valid());
+        assert!(report.on_curve);
+        assert_eq!(report.in_subgroup, Some(false));
+        assert_eq!(
+            format!("{}", report),
+            "the point is not in the prime-order subgroup"
+        );
+    }
+
+    let report = ValidationReport::new::<G>(&G::identity().to_bytes());
+    assert!(!report.is_valid());
+    assert!(report.on_curve);
+    assert_eq!(report.not_identity, Some(false));
+    assert_eq!(format!("{}", report), "the point is the identity");
+
+    // Find an encoding that does not decode to a point.
+    let mut repr = G::Repr::default();
+    loop {
+        rng.fill(repr.as_mut());
+        if bool::from(G::from_bytes(&repr).is_none()) {
+            break;
+        }
+    }
+    let report = ValidationReport::new::<G>(&repr);
+    assert!(!report.is_valid());
+    assert_eq!(
+        report,
+        ValidationReport {
+            on_curve: false,
+            in_subgroup: None,
+            canonical: None,
+            not_identity: None,
+        }
+    );
+}
+
+pub fn encoding_sort_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    sort_by_encoding::<G::Affine>(&mut []);
+    let mut empty = Vec::<G::Affine>::new();
+    sort_dedup_by_encoding(&mut empty);
+    assert!(empty.is_empty());
+
+    let distinct = (0..50)
+        .map(|_| G::random(&mut rng).to_affine())
+        .chain(Some(G::Affine::identity()))
+        .collect::<Vec<_>>();
+
+    // Repeat some of the points, in a shuffled order.
+    let mut v = distinct
+        .iter()
+        .chain(distinct.iter().step_by(3))
+        .chain(distinct.iter().step_by(7))
+        .cloned()
+        .collect::<Vec<_>>();
+    for i in (1..v.len()).rev() {
+        v.swap(i, rng.gen_range(0..=i));
+    }
+
+    let mut expected = v
+        .iter()
+        .map(|p| p.to_bytes().as_ref().to_vec())
+        .collect::<Vec<_>>();
+    expected.sort();
+
+    sort_by_encoding(&mut v);
+    assert_eq!(
+        v.iter()
+            .map(|p| p.to_bytes().as_ref().to_vec())
+            .collect::<Vec<_>>(),
+        expected
+    );
+
+    sort_dedup_by_encoding(&mut v);
+    expected.dedup();
+    assert_eq!(v.len(), distinct.len());
+    assert_eq!(
+        v.iter()
+            .map(|p| p.to_bytes().as_ref().to_vec())
+            .collect::<Vec<_>>(),
+        expected
+    );
+    for p in distinct.iter() {
+        assert!(v.contains(p));
+    }
+}
+
+pub fn terms_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut terms = Terms::<G>::default();
+    assert!(terms.is_empty());
+    assert!(bool::from(terms.evaluate().is_identity()));
+
+    for _ in 0..10 {
+        let points = (0..10)
+            .map(|_| G::random(&mut rng).to_affine())
+            .collect::<Vec<_>>();
+
+        let mut terms = Terms::<G>::with_capacity(40);
+        let mut expected = G::identity();
+        for _ in 0..30 {
+            let s = G::Scalar::random(&mut rng);
+            let p = points[rng.gen_range(0..points.len())];
+            terms.add_term(s, p);
+            expected += p * s;
+        }
+
+        // Vanishing terms.
+        let s = G::Scalar::random(&mut rng);
+        terms
+            .add_term(G::Scalar::ZERO, points[0])
+            .add_term(s, G::Affine::identity())
+            .add_term(s, points[1])
+            .add_term(-s, points[1]);
+        terms.extend(Some((s, points[2])));
+        expected += points[2] * s;
+        assert_eq!(terms.len(), 35);
+
+        let mut simplified = terms.clone();
+        simplified.simplify();
+        assert!(simplified.len() <= points.len());
+        let encodings = simplified
+            .terms()
+            .iter()
+            .map(|t| t.point.to_bytes().as_ref().to_vec())
+            .collect::<Vec<_>>();
+        assert!(encodings.windows(2).all(|w| w[0] < w[1]));
+        for t in simplified.terms() {
+            assert!(!bool::from(t.scalar.is_zero()));
+            assert!(!bool::from(t.point.is_identity()));
+        }
+
+        assert_eq!(terms.evaluate(), expected);
+        assert_eq!(terms.len(), simplified.len());
+    }
+}
+
+pub fn msm_job_tests<G: PrimeCurve>()
+where
+    G::Affine: TaggedEncoding + UncompressedEncoding,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let formats = [false, true].iter().flat_map(|&uncompressed| {
+        [false, true]
+            .iter()
+            .map(move |&scalars_big_endian| MsmFormat {
+                uncompressed,
+                scalars_big_endian,
+            })
+    });
+
+    for format in formats {
+        for len in [0, 1, 10].iter() {
+            let bases = (0..*len)
+                .map(|_| G::random(&mut rng).to_affine())
+                .collect::<Vec<_>>();
+            let scalars = (0..*len)
+                .map(|_| G::Scalar::random(&mut rng))
+                .collect::<Vec<_>>();
+            let job = MsmJob::<G>::new(bases, scalars);
+
+            let bytes = job.to_bytes(format);
+            assert_eq!(&bytes[..4], &G::Affine::CURVE_ID);
+            assert_eq!(bytes[4], msm_job::VERSION);
+            assert_eq!(&bytes[6..14], &(*len as u64).to_le_bytes());
+
+            let (decoded, decoded_format) = MsmJob::<G>::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded_format, format);
+            assert_eq!(decoded.bases, job.bases);
+            assert_eq!(decoded.scalars, job.scalars);
+            assert_eq!(decoded.evaluate(), job.evaluate());
+
+            // The first scalar is written in the requested byte order.
+            if *len > 0 {
+                let scalar_len = G::Scalar::ONE.to_repr().as_ref().len();
+                let one = MsmJob::<G>::new(vec![G::Affine::generator()], vec![G::Scalar::ONE])
+                    .to_bytes(format);
+                let scalar = &one[one.len() - scalar_len..];
+                if format.scalars_big_endian {
+                    assert_eq!(scalar[scalar_len - 1], 1);
+                } else {
+                    assert_eq!(scalar[0], 1);
+                }
+            }
+
+            if *len > 0 {
+                assert_eq!(
+                    MsmJob::<G>::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+                    MsmJobError::Truncated
+                );
+            }
+            let mut trailing = bytes.clone();
+            trailing.push(0);
+            assert_eq!(
+                MsmJob::<G>::from_bytes(&trailing).unwrap_err(),
+                MsmJobError::TrailingBytes
+            );
+        }
+    }
+
+    let job = MsmJob::<G>::new(
+        vec![G::Affine::generator(); 2],
+        vec![G::Scalar::ONE, -G::Scalar::ONE],
+    );
+    assert!(bool::from(job.evaluate().is_identity()));
+    let bytes = job.to_bytes(MsmFormat::default());
+
+    assert_eq!(
+        MsmJob::<G>::from_bytes(&bytes[..msm_job::HEADER_LEN - 1]).unwrap_err(),
+        MsmJobError::Truncated
+    );
+
+    let mut wrong_curve = bytes.clone();
+    wrong_curve[0] ^= 1;
+    let mut found = G::Affine::CURVE_ID;
+    found[0] ^= 1;
+    assert_eq!(
+        MsmJob::<G>::from_bytes(&wrong_curve).unwrap_err(),
+        MsmJobError::CurveMismatch {
+            expected: G::Affine::CURVE_ID,
+            found,
+        }
+    );
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[4] = msm_job::VERSION + 1;
+    assert_eq!(
+        MsmJob::<G>::from_bytes(&wrong_version).unwrap_err(),
+        MsmJobError::UnsupportedVersion(msm_job::VERSION + 1)
+    );
+
+    let mut wrong_flags = bytes.clone();
+    wrong_flags[5] = 0x80;
+    assert_eq!(
+        MsmJob::<G>::from_bytes(&wrong_flags).unwrap_err(),
+        MsmJobError::UnsupportedFlags(0x80)
+    );
+
+    let mut huge = bytes.clone();
+    huge[6..14].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert_eq!(
+        MsmJob::<G>::from_bytes(&huge).unwrap_err(),
+        MsmJobError::Truncated
+    );
+
+    // A non-canonical scalar is rejected.
+    let mut invalid_scalar = bytes;
+    let scalar_len = G::Scalar::ONE.to_repr().as_ref().len();
+    let start = invalid_scalar.len() - scalar_len;
+    for b in invalid_scalar[start..].iter_mut() {
+        *b = 0xff;
+    }
+    assert_eq!(
+        MsmJob::<G>::from_bytes(&invalid_scalar).unwrap_err(),
+        MsmJobError::InvalidScalar(1)
+    );
+}
+
+pub fn tagged_encoding_tests<G: Group + TaggedEncoding>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = G::random(&mut rng);
+        let bytes = p.to_tagged_bytes();
+        assert_eq!(&bytes[..4], &G::CURVE_ID);
+        assert_eq!(bytes[4], VERSION);
+        assert_eq!(bytes[5], 0);
+        assert_eq!(&bytes[HEADER_LEN..], p.to_bytes().as_ref());
+        assert_eq!(G::from_tagged_bytes(&bytes), Ok(p));
+    }
+
+    let bytes = G::random(&mut rng).to_tagged_bytes();
+    let expected_len = bytes.len();
+
+    assert_eq!(
+        G::from_tagged_bytes(&bytes[1..]),
+        Err(EnvelopeError::InvalidLength {
+            expected: expected_len,
+            found: expected_len - 1,
+        })
+    );
+
+    let mut wrong_curve = bytes.clone();
+    wrong_curve[0] ^= 1;
+    let mut found = G::CURVE_ID;
+    found[0] ^= 1;
+    assert_eq!(
+        G::from_tagged_bytes(&wrong_curve),
+        Err(EnvelopeError::CurveMismatch {
+            expected: G::CURVE_ID,
+            found,
+        })
+    );
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[4] = VERSION + 1;
+    assert_eq!(
+        G::from_tagged_bytes(&wrong_version),
+        Err(EnvelopeError::UnsupportedVersion(VERSION + 1))
+    );
+
+    let mut wrong_flags = bytes;
+    wrong_flags[5] = 0x80;
+    assert_eq!(
+        G::from_tagged_bytes(&wrong_flags),
+        Err(EnvelopeError::UnsupportedFlags(0x80))
+    );
+
+    // Find a payload that does not decode to a point.
+    let mut invalid = G::identity().to_tagged_bytes();
+    loop {
+        rng.fill(&mut invalid[HEADER_LEN..]);
+        let mut repr = G::Repr::default();
+        repr.as_mut().copy_from_slice(&invalid[HEADER_LEN..]);
+        if bool::from(G::from_bytes(&repr).is_none()) {
+            break;
+        }
+    }
+    assert_eq!(
+        G::from_tagged_bytes(&invalid),
+        Err(EnvelopeError::InvalidPoint)
+    );
+}
+
+/// Checks the operation counts of the generic algorithms in this crate.
+///
+/// The counters are global, so this must not run concurrently with other code that uses
+/// this crate.
+#[cfg(feature = "trace")]
+pub fn trace_tests<G: PrimeCurve + ConditionallySelectable>() {
+    use crate::trace::{self, Counts};
+    use crate::wnaf::WnafBase;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = G::random(&mut rng);
+    let s = G::Scalar::random(&mut rng);
+    let bits = G::Scalar::NUM_BITS as usize;
+
+    trace::reset();
+    assert_eq!(trace::counts(), Counts::default());
+
+    let (_, counts) = trace::measure(|| ladder(&p, &s));
+    assert_eq!(
+        counts,
+        Counts {
+            additions: bits,
+            doublings: bits,
+            inversions: 0,
+            table_lookups: 0,
+        }
+    );
+
+    let (table, counts) = trace::measure(|| LookupTable::<G, 8>::new(p));
+    assert_eq!(counts.additions, 7);
+    let (_, counts) = trace::measure(|| table.select(-3));
+    assert_eq!(counts.table_lookups, 1);
+
+    let (wnaf_base, counts) = trace::measure(|| WnafBase::<G, 4>::new(p));
+    assert_eq!(counts.doublings, 1);
+    assert_eq!(counts.additions, 8);
+    let (_, counts) = trace::measure(|| wnaf_base.scalar(&s));
+    assert!(counts.doublings < bits + 4);
+    assert!(counts.additions < bits / 2);
+
+    // Linear combinations with short scalars skip the unused high positions: one
+    // doubling per table, and at most 129 for the w-NAF digits of 128-bit scalars.
+    let mut terms = Terms::<G>::new();
+    terms
+        .add_term(G::Scalar::from_u128(rng.gen()), p.to_affine())
+        .add_term(G::Scalar::from_u128(rng.gen()), G::generator().to_affine());
+    let (_, counts) = trace::measure(|| terms.evaluate());
+    assert!(counts.doublings <= 2 + 129);
+
+    let mut v = (0..10)
+        .map(|_| G::Scalar::random(&mut rng))
+        .collect::<Vec<_>>();
+    let (_, counts) = trace::measure(|| batch_invert(&mut v));
+    assert_eq!(counts.inversions, 1);
+
+    trace::reset();
+    assert_eq!(trace::counts(), Counts::default());
+}
+
+pub fn from_x_tests<A: ShortWeierstrass + PartialEq + core::fmt::Debug>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut recovered = 0;
+    let mut rejected = 0;
+    for _ in 0..200 {
+        let x = A::Base::random(&mut rng);
+        let is_square = (x.square() * x + A::a() * x + A::b()).sqrt().is_some();
+
+        let even = A::from_x(x, Choice::from(0));
+        let odd = A::from_x(x, Choice::from(1));
+        assert_eq!(bool::from(even.is_some()), bool::from(odd.is_some()));
+
+        if bool::from(even.is_some()) {
+            recovered += 1;
+            let (even, odd) = (even.unwrap(), odd.unwrap());
+            assert!(!bool::from(even.y_parity()));
+            assert!(bool::from(odd.y_parity()));
+
+            let (ex, ey) = even.coordinates().unwrap();
+            let (ox, oy) = odd.coordinates().unwrap();
+            assert_eq!(ex, x);
+            assert_eq!(ox, x);
+            assert_eq!(oy, -ey);
+            assert_eq!(ey.square(), x.square() * x + A::a() * x + A::b());
+            assert_eq!(A::from_coordinates(ex, ey).unwrap(), even);
+        } else if !bool::from(is_square) {
+            rejected += 1;
+        }
+    }
+
+    // Roughly half of all x-coordinates are on the curve.
+    assert!(recovered > 50);
+    assert!(rejected > 50);
+
+    // Batch recovery
+    {
+        assert!(A::batch_from_x(&[]).unwrap().is_empty());
+
+        let mut xs = vec![];
+        while xs.len() < 100 {
+            let x = A::Base::random(&mut rng);
+            let y_is_odd = Choice::from(rng.gen::<u8>() & 1);
+            if bool::from(A::from_x(x, y_is_odd).is_some()) {
+                xs.push((x, y_is_odd));
+            }
+        }
+
+        let expected = xs
+            .iter()
+            .map(|&(x, y_is_odd)| A::from_x(x, y_is_odd).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(A::batch_from_x(&xs).unwrap(), expected);
+
+        // Find an x-coordinate that is not on the curve.
+        loop {
+            let x = A::Base::random(&mut rng);
+            if bool::from(A::from_x(x, Choice::from(0)).is_none()) {
+                xs[50].0 = x;
+                break;
+            }
+        }
+        assert!(bool::from(A::batch_from_x(&xs).is_none()));
+    }
+}
+
+pub fn adversarial_from_x_tests<A: ShortWeierstrass + PartialEq + core::fmt::Debug>()
+where
+    A::Base: PrimeField,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let rhs = |x: A::Base| x.square() * x + A::a() * x + A::b();
+    let check = |x: A::Base| {
+        let is_square = bool::from(rhs(x).sqrt().is_some());
+        for &parity in [0, 1].iter() {
+            let p = A::from_x(x, Choice::from(parity));
+            if bool::from(p.is_some()) {
+                // Accepted x-coordinates must be on the curve, with the requested parity.
+                assert!(is_square);
+                let p = p.unwrap();
+                assert_eq!(bool::from(p.y_parity()), parity == 1);
+                let (px, py) = p.coordinates().unwrap();
+                assert_eq!(px, x);
+                assert_eq!(py.square(), rhs(x));
+                assert_eq!(A::from_coordinates(px, py).unwrap(), p);
+            } else if !is_square {
+                assert!(bool::from(
+                    A::batch_from_x(&[(x, Choice::from(parity))]).is_none()
+                ));
+            }
+        }
+    };
+
+    // Edge values of x.
+    let zero = A::Base::ZERO;
+    let one = A::Base::ONE;
+    for x in [zero, one, -one, one.double(), -one.double()].iter() {
+        check(*x);
+    }
+
+    // x-coordinates whose right-hand side is not a square must always be rejected.
+    let mut non_squares = 0;
+    while non_squares < 50 {
+        let x = A::Base::random(&mut rng);
+        if bool::from(rhs(x).sqrt().is_none()) {
+            non_squares += 1;
+            assert!(bool::from(A::from_x(x, Choice::from(0)).is_none()));
+            assert!(bool::from(A::from_x(x, Choice::from(1)).is_none()));
+        }
+        check(x);
+    }
+
+    // Encodings of the modulus and of values near it. The representation's endianness
+    // is inferred from the encoding of one.
+    let little_endian = one.to_repr().as_ref()[0] == 1;
+    let mut modulus = <A::Base as PrimeField>::Repr::default();
+    {
+        let bytes = modulus.as_mut();
+        let len = bytes.len();
+        let digits = A::Base::MODULUS.trim_start_matches("0x").as_bytes();
+        for (i, pair) in digits.rchunks(2).enumerate() {
+            let pair = core::str::from_utf8(pair).unwrap();
+            bytes[len - 1 - i] = u8::from_str_radix(pair, 16).unwrap();
+        }
+        if little_endian {
+            bytes.reverse();
+        }
+    }
+
+    // Adds `delta` to the integer encoded by `repr`, wrapping on overflow.
+    let offset = |repr: &<A::Base as PrimeField>::Repr, delta: i8| {
+        let mut repr = *repr;
+        let bytes = repr.as_mut();
+        if !little_endian {
+            bytes.reverse();
+        }
+        let mut carry = delta as i16;
+        for b in bytes.iter_mut() {
+            let v = *b as i16 + carry;
+            *b = v.rem_euclid(256) as u8;
+            carry = v.div_euclid(256);
+        }
+        if !little_endian {
+            bytes.reverse();
+        }
+        repr
+    };
+
+    assert!(bool::from(A::Base::from_repr(modulus).is_none()));
+    assert!(bool::from(
+        A::Base::from_repr(offset(&modulus, 1)).is_none()
+    ));
+    assert_eq!(A::Base::from_repr(offset(&modulus, -1)).unwrap(), -one);
+    let mut all_ones = <A::Base as PrimeField>::Repr::default();
+    all_ones.as_mut().iter_mut().for_each(|b| *b = 0xff);
+    assert!(bool::from(A::Base::from_repr(all_ones).is_none()));
+    check(A::Base::from_repr(offset(&modulus, -1)).unwrap());
+}
+
+pub fn curve_constants_tests<G: PrimeCurve>()
+where
+    G::Affine: ShortWeierstrass,
+{
+    let (a, b) = (
+        <G::Affine as ShortWeierstrass>::a(),
+        <G::Affine as ShortWeierstrass>::b(),
+    );
+
+    // The curve is non-singular.
+    let one = <G::Affine as AffineCoordinates>::Base::ONE;
+    let four = one.double().double();
+    let three = one.double() + one;
+    let twenty_seven = three.square() * three;
+    assert!(!bool::from(
+        (four * a.square() * a + twenty_seven * b.square()).is_zero()
+    ));
+
+    // The generator is on the curve.
+    let g = G::Affine::generator();
+    assert!(!bool::from(g.is_identity()));
+    assert_eq!(g.to_curve(), G::generator());
+    let (x, y) = g.coordinates().unwrap();
+    assert_eq!(y.square(), x.square() * x + a * x + b);
+    assert_eq!(G::Affine::from_coordinates(x, y).unwrap(), g);
+
+    // The generator has the order of the scalar field, computed from the hexadecimal
+    // modulus by double-and-add.
+    let modulus = G::Scalar::MODULUS;
+    assert!(modulus.starts_with("0x"));
+    let mut acc = G::identity();
+    for digit in modulus[2..].chars() {
+        let digit = digit.to_digit(16).expect("MODULUS is hexadecimal");
+        for i in (0..4).rev() {
+            acc = acc.double();
+            if (digit >> i) & 1 == 1 {
+                acc += G::generator();
+            }
+        }
+    }
+    assert!(bool::from(acc.is_identity()));
+}
+
+pub fn elligator_tests<G: Group + Elligator>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut representable = 0;
+    for _ in 0..200 {
+        let p = G::random(&mut rng);
+        let r = p.to_representative(&mut rng);
+        if bool::from(r.is_some()) {
+            representable += 1;
+            assert_eq!(G::from_representative(&r.unwrap()), p);
+        }
+    }
+
+    // Elligator 2 represents roughly half of all points.
+    assert!(representable > 50);
+
+    // Every byte string is a representative of some point.
+    for _ in 0..100 {
+        let mut r = G::Representative::default();
+        rng.fill_bytes(r.as_mut());
+        let p = G::from_representative(&r);
+        if let Some(r2) = Option::from(p.to_representative(&mut rng)) {
+            assert_eq!(G::from_representative(&r2), p);
+        }
+    }
+}
+
+pub fn non_identity_tests<G: PrimeCurve>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(bool::from(NonIdentity::new(G::identity()).is_none()));
+    assert!(bool::from(
+        NonIdentity::<G>::from_bytes(&G::identity().to_bytes()).is_none()
+    ));
+    assert_eq!(NonIdentity::<G>::generator().get(), G::generator());
+
+    for _ in 0..100 {
+        let p = NonIdentity::<G>::random(&mut rng);
+        assert!(!bool::from(p.is_identity()));
+        assert_eq!(NonIdentity::new(*p).unwrap(), p);
+        assert_eq!(NonIdentity::<G>::from_bytes(&p.to_bytes()).unwrap(), p);
+        assert_eq!(p.to_affine().get(), p.get().to_affine());
+        assert!(bool::from(p.ct_eq(&p)));
+
+        let q = NonIdentity::<G>::random(&mut rng);
+        let s = G::Scalar::random(&mut rng);
+        assert_eq!(p + q, p.get() + q.get());
+        assert_eq!(p + q.get(), p.get() + q.get());
+        assert_eq!(p - q, p.get() - q.get());
+        assert_eq!(p - q.get(), p.get() - q.get());
+        assert_eq!(p * s, p.get() * s);
+        assert!(bool::from((p + (-p)).is_identity()));
+        assert!(bool::from((p - p.get()).is_identity()));
+    }
+}
+
+pub fn transcript_tests<G: Group + GroupEncoding>() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    #[derive(Default)]
+    struct Recorder(Vec<(&'static [u8], Vec<u8>)>);
+
+    impl Transcript for Recorder {
+        fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+            self.0.push((label, message.to_vec()));
+        }
+    }
+
+    let p = G::random(&mut rng);
+    let mut transcript = Recorder::default();
+    p.append_to_transcript(b"point", &mut transcript);
+    assert_eq!(
+        transcript.0,
+        vec![(&b"point"[..], p.to_bytes().as_ref().to_vec())]
+    );
+
+    let points = (0..10).map(|_| G::random(&mut rng)).collect::<Vec<_>>();
+    let mut transcript = Recorder::default();
+    points[..].append_to_transcript(b"points", &mut transcript);
+    assert_eq!(transcript.0.len(), 11);
+    assert_eq!(
+        transcript.0[0],
+        (&b"points"[..], 10u64.to_le_bytes().to_vec())
+    );
+    for (message, p) in transcript.0[1..].iter().zip(points.iter()) {
+        assert_eq!(message, &(&b"points"[..], p.to_bytes().as_ref().to_vec()));
+    }
+
+    // Splitting a vector differently changes the transcript.
+    let mut split = Recorder::default();
+    points[..5].append_to_transcript(b"points", &mut split);
+    points[5..].append_to_transcript(b"points", &mut split);
+    assert_ne!(transcript.0, split.0);
+}
+
+pub fn keypair_tests<G: PrimeCurve>()
+where
+    G::Scalar: FromUniformBytes<64>,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(bool::from(
+        Keypair::<G>::from_secret(G::Scalar::ZERO).is_none()
+    ));
+    assert!(bool::from(
+        Keypair::<G>::from_bytes(&G::Scalar::ZERO.to_repr()).is_none()
+    ));
+    assert!(bool::from(Keypair::<G>::from_seed(&[0; 64]).is_none()));
+
+    for _ in 0..100 {
+        let keypair = Keypair::<G>::random(TestCryptoRng(&mut rng));
+        assert!(!bool::from(keypair.secret().is_zero()));
+        assert_eq!(
+            *keypair.public(),
+            (G::generator() * keypair.secret()).to_affine()
+        );
+
+        let parsed = Keypair::<G>::from_bytes(&keypair.to_bytes()).unwrap();
+        assert!(bool::from(parsed.ct_eq(&keypair)));
+        assert_eq!(parsed.public(), keypair.public());
+
+        let mut seed = [0; 64];
+        rng.fill(&mut seed[..]);
+        let from_seed = Keypair::<G>::from_seed(&seed).unwrap();
+        assert_eq!(*from_seed.secret(), G::Scalar::from_uniform_bytes(&seed));
+        assert!(bool::from(
+            Keypair::<G>::from_seed(&seed)
+                .unwrap()
+                .ct_eq(&from_seed.clone())
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+//! Operation counters for the generic algorithms in this crate.
+//!
+//! When the `trace` feature is enabled, the algorithms implemented in this crate (w-NAF
+//! multiplication, the Montgomery ladder, lookup tables, batch inversion, and the
+//! `ψ`-based BLS12 helpers) count the group and field operations they perform. Comparing
+//! operation counts is a more reliable way to evaluate an algorithmic change than
+//! wall-clock timing:
+//!
+//! ```ignore
+//! let (result, counts) = group::trace::measure(|| wnaf_base.scalar(&s));
+//! assert_eq!(counts.doublings, 254);
+//! ```
+//!
+//! Only operations performed by this crate are counted; arithmetic performed directly by
+//! a curve implementation is not. The counters are global, so measurements taken while
+//! other threads are using this crate will include their operations too.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+pub(crate) static ADDITIONS: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static DOUBLINGS: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static INVERSIONS: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static TABLE_LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+
+/// A snapshot of the operation counters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    /// The number of group additions and subtractions.
+    pub additions: usize,
+    /// The number of group doublings.
+    pub doublings: usize,
+    /// The number of field inversions.
+    pub inversions: usize,
+    /// The number of constant-time table lookups.
+    pub table_lookups: usize,
+}
+
+/// Returns the current values of the global operation counters.
+pub fn counts() -> Counts {
+    Counts {
+        additions: ADDITIONS.load(Ordering::Relaxed),
+        doublings: DOUBLINGS.load(Ordering::Relaxed),
+        inversions: INVERSIONS.load(Ordering::Relaxed),
+        table_lookups: TABLE_LOOKUPS.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets the global operation counters to zero.
+pub fn reset() {
+    ADDITIONS.store(0, Ordering::Relaxed);
+    DOUBLINGS.store(0, Ordering::Relaxed);
+    INVERSIONS.store(0, Ordering::Relaxed);
+    TABLE_LOOKUPS.store(0, Ordering::Relaxed);
+}
+
+/// Runs `f`, returning its result along with the operations it performed.
+///
+/// The counts are the differences of the global counters before and after `f` runs. If
+/// another thread calls [`reset`] while `f` runs, they are meaningless, but this does not
+/// panic.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Counts) {
+    let before = counts();
+    let result = f();
+    let after = counts();
+
+    (
+        result,
+        Counts {
+            additions: after.additions.wrapping_sub(before.additions),
+            doublings: after.doublings.wrapping_sub(before.doublings),
+            inversions: after.inversions.wrapping_sub(before.inversions),
+            table_lookups: after.table_lookups.wrapping_sub(before.table_lookups),
+        },
+    )
+}
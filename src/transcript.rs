@@ -0,0 +1,63 @@
+//! Absorbing group elements into Fiat–Shamir transcripts.
+//!
+//! Protocols built on this crate should absorb group elements with [`TranscriptAppend`],
+//! so that they all commit to points in the same way and produce interoperable
+//! transcripts.
+
+use crate::GroupEncoding;
+
+/// A Fiat–Shamir transcript that absorbs labelled messages.
+///
+/// Implementations must unambiguously frame each message with its label and length, as
+/// [Merlin] transcripts do; an implementation for `merlin::Transcript` is provided behind
+/// the `merlin` feature flag.
+///
+/// [Merlin]: https://merlin.cool
+pub trait Transcript {
+    /// Appends a message to the transcript under the given label.
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+}
+
+#[cfg(feature = "merlin")]
+impl Transcript for merlin::Transcript {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        merlin::Transcript::append_message(self, label, message)
+    }
+}
+
+/// Values that can be absorbed into a [`Transcript`] in a canonical way.
+pub trait TranscriptAppend {
+    /// Appends this value to the transcript under the given label.
+    fn append_to_transcript<T: Transcript + ?Sized>(
+        &self,
+        label: &'static [u8],
+        transcript: &mut T,
+    );
+}
+
+/// Group elements are absorbed as a single message containing their [`GroupEncoding`].
+impl<G: GroupEncoding> TranscriptAppend for G {
+    fn append_to_transcript<T: Transcript + ?Sized>(
+        &self,
+        label: &'static [u8],
+        transcript: &mut T,
+    ) {
+        transcript.append_message(label, self.to_bytes().as_ref());
+    }
+}
+
+/// Slices of group elements are absorbed as a message containing the number of elements
+/// (as a little-endian `u64`), followed by a message for each element, all under the
+/// same label.
+impl<G: GroupEncoding> TranscriptAppend for [G] {
+    fn append_to_transcript<T: Transcript + ?Sized>(
+        &self,
+        label: &'static [u8],
+        transcript: &mut T,
+    ) {
+        transcript.append_message(label, &(self.len() as u64).to_le_bytes());
+        for p in self {
+            p.append_to_transcript(label, transcript);
+        }
+    }
+}
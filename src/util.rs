@@ -0,0 +1,484 @@
+//! Utilities for working with groups and their elements.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use ff::Field;
+use ff::PrimeField;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::{Group, GroupEncoding};
+
+/// Determines if `a[i] == b[i]` for every `i`, in constant time.
+///
+/// Every pair of elements is compared, without exiting early on the first mismatch.
+/// Returns false if `a` and `b` have different lengths; the lengths of the slices are
+/// not treated as secret.
+pub fn ct_eq_all<T: ConstantTimeEq>(a: &[T], b: &[T]) -> Choice {
+    if a.len() != b.len() {
+        return Choice::from(0);
+    }
+
+    a.iter()
+        .zip(b.iter())
+        .fold(Choice::from(1), |acc, (a, b)| acc & a.ct_eq(b))
+}
+
+/// Decodes a secret encoding without branching on whether it is valid.
+///
+/// Returns the decoded element and `1`, or the generator and `0` if `bytes` is not a
+/// valid encoding. The caller carries the returned [`Choice`] through the rest of its
+/// computation (for example into an MPC protocol's abort flag), instead of branching on
+/// it. The dummy element is a valid group element, so subsequent arithmetic behaves
+/// normally whether or not decoding succeeded.
+///
+/// This is only constant time if [`GroupEncoding::from_bytes`] is.
+pub fn from_bytes_blinded<G>(bytes: &G::Repr) -> (G, Choice)
+where
+    G: Group + GroupEncoding + ConditionallySelectable,
+{
+    let decoded = G::from_bytes(bytes);
+    let is_valid = decoded.is_some();
+    (decoded.unwrap_or(G::generator()), is_valid)
+}
+
+/// Inverts every element of `elements` in place, using Montgomery's trick to perform a
+/// single field inversion for the whole batch.
+///
+/// Zero elements have no inverse, and are left as zero. This runs in constant time with
+/// respect to the values of the elements, including which of them are zero.
+#[cfg(feature = "alloc")]
+pub fn batch_invert<F: Field>(elements: &mut [F]) {
+    // Compute the running products of the non-zero elements, remembering the product of
+    // the elements preceding each one.
+    let mut scratch = Vec::with_capacity(elements.len());
+    let mut acc = F::ONE;
+    for e in elements.iter() {
+        scratch.push(acc);
+        acc = F::conditional_select(&(acc * e), &acc, e.is_zero());
+    }
+
+    // The product of non-zero elements is non-zero.
+    acc = acc.invert().unwrap();
+    trace!(INVERSIONS);
+
+    // Walk backwards, peeling one element off the inverted product at a time.
+    for (e, prefix) in elements.iter_mut().zip(scratch).rev() {
+        let is_zero = e.is_zero();
+        let inv = acc * prefix;
+        acc = F::conditional_select(&(acc * *e), &acc, is_zero);
+        *e = F::conditional_select(&inv, e, is_zero);
+    }
+}
+
+/// Sorts `points` in place by their canonical encodings, compared lexicographically.
+///
+/// Each point is encoded once, rather than once per comparison. Points with equal
+/// encodings are adjacent afterwards, but their relative order is not preserved. This
+/// runs in variable time, and must only be used with public points.
+#[cfg(feature = "alloc")]
+pub fn sort_by_encoding<G: GroupEncoding>(points: &mut [G]) {
+    sort_by_encoding_key(points, G::to_bytes);
+}
+
+/// Sorts `points` by their canonical encodings as with [`sort_by_encoding`], and then
+/// removes every point whose encoding equals that of its predecessor.
+///
+/// This runs in variable time, and must only be used with public points.
+#[cfg(feature = "alloc")]
+pub fn sort_dedup_by_encoding<G: GroupEncoding>(points: &mut Vec<G>) {
+    let encodings = sort_by_encoding_key(points, G::to_bytes);
+
+    let mut i = 0;
+    points.retain(|_| {
+        let keep = i == 0 || encodings[i].as_ref() != encodings[i - 1].as_ref();
+        i += 1;
+        keep
+    });
+}
+
+/// Sorts `items` by the encodings returned by `encode`, returning the sorted encodings.
+#[cfg(feature = "alloc")]
+pub(crate) fn sort_by_encoding_key<T, R: AsRef<[u8]>>(
+    items: &mut [T],
+    encode: impl Fn(&T) -> R,
+) -> Vec<R> {
+    let mut encodings = items.iter().map(encode).collect::<Vec<_>>();
+    let mut perm = (0..items.len()).collect::<Vec<_>>();
+    perm.sort_unstable_by(|&a, &b| encodings[a].as_ref().cmp(encodings[b].as_ref()));
+
+    // Apply the permutation in place by following its cycles, so that the points need
+    // not be cloned. Afterwards, `items[i]` is the original `items[perm[i]]`.
+    for start in 0..perm.len() {
+        let mut cur = start;
+        while perm[cur] != start {
+            let next = perm[cur];
+            items.swap(cur, next);
+            encodings.swap(cur, next);
+            perm[cur] = cur;
+            cur = next;
+        }
+        perm[cur] = cur;
+    }
+
+    encodings
+}
+
+/// Returns an iterator over the `window`-bit windows of `scalar`, from least to most
+/// significant.
+///
+/// The scalar is read from its [`PrimeField::to_repr`] encoding, which is assumed to be
+/// little-endian. Only the low `NUM_BITS` bits are read, so there are
+/// `ceil(NUM_BITS / window)` windows, and the most significant window holds the
+/// remaining `NUM_BITS % window` bits (or `window` bits, if `window` divides
+/// `NUM_BITS`). Iterate in reverse with [`Iterator::rev`] for most-significant-first
+/// algorithms.
+///
+/// Extracting a window does not branch on the scalar, but what callers do with it
+/// usually does. This function will panic if `window` is not in `1..=64`.
+pub fn scalar_windows<F: PrimeField>(scalar: &F, window: usize) -> ScalarWindows<F> {
+    assert!((1..=64).contains(&window));
+
+    let num_bits = F::NUM_BITS as usize;
+    ScalarWindows {
+        repr: scalar.to_repr(),
+        window,
+        front: 0,
+        back: (num_bits + window - 1) / window,
+    }
+}
+
+/// An iterator over the windows of a scalar, returned by [`scalar_windows`].
+#[derive(Clone, Debug)]
+pub struct ScalarWindows<F: PrimeField> {
+    repr: F::Repr,
+    window: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<F: PrimeField> ScalarWindows<F> {
+    fn get(&self, index: usize) -> u64 {
+        let start = index * self.window;
+        let len = core::cmp::min(self.window, F::NUM_BITS as usize - start);
+        read_bits(self.repr.as_ref(), start, len)
+    }
+}
+
+/// Returns the `len` bits of the little-endian integer `bytes` starting at bit `start`,
+/// where `1 <= len <= 64`. Bits beyond the end of `bytes` are read as zero.
+fn read_bits(bytes: &[u8], start: usize, len: usize) -> u64 {
+    debug_assert!((1..=64).contains(&len));
+
+    // A window spans at most nine bytes.
+    let bytes = bytes.get(start / 8..).unwrap_or(&[]);
+    let acc = bytes
+        .iter()
+        .take(9)
+        .enumerate()
+        .fold(0u128, |acc, (i, b)| acc | (u128::from(*b) << (8 * i)));
+
+    ((acc >> (start % 8)) as u64) & (u64::MAX >> (64 - len))
+}
+
+impl<F: PrimeField> Iterator for ScalarWindows<F> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.front == self.back {
+            return None;
+        }
+        let window = self.get(self.front);
+        self.front += 1;
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<F: PrimeField> DoubleEndedIterator for ScalarWindows<F> {
+    fn next_back(&mut self) -> Option<u64> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.get(self.back))
+    }
+}
+
+impl<F: PrimeField> ExactSizeIterator for ScalarWindows<F> {}
+
+/// Returns the joint sparse form of the pair of scalars `(a, b)`, as an iterator over
+/// pairs of signed digits in `{-1, 0, 1}` from least to most significant.
+///
+/// The joint sparse form (Solinas, 2001) is the signed binary representation of a pair of
+/// integers with the fewest positions in which either digit is non-zero: on average half
+/// of the positions, compared to five ninths for independent NAF recodings and three
+/// quarters for plain binary. Multiplications sharing one chain of doublings, such as
+/// [`double_scalar_mul_vartime`](crate::mul::double_scalar_mul_vartime), perform one
+/// addition per non-zero position.
+///
+/// The scalars are read from their [`PrimeField::to_repr`] encodings, which are assumed
+/// to be little-endian. There are `NUM_BITS + 1` positions, as the recoding may carry
+/// into the position above the most significant bit. Iterate in reverse with
+/// [`Iterator::rev`] for most-significant-first algorithms. The recoding branches on the
+/// scalars, and must only be used with public scalars.
+pub fn joint_sparse_form<F: PrimeField>(a: &F, b: &F) -> JointSparseForm<F> {
+    fn bit(repr: &[u8], i: usize) -> u8 {
+        repr.get(i / 8).map_or(0, |byte| (byte >> (i % 8)) & 1)
+    }
+
+    let reprs = [a.to_repr(), b.to_repr()];
+    let mut jsf = JointSparseForm {
+        nonzero: [F::Repr::default(), F::Repr::default()],
+        negative: [F::Repr::default(), F::Repr::default()],
+        top: (0, 0),
+        front: 0,
+        back: F::NUM_BITS as usize + 1,
+    };
+
+    // The carries into the current position.
+    let mut carries = [0u8; 2];
+    for i in 0..jsf.back {
+        // The current position and the two above it, plus the carry, modulo 8.
+        let l = [0, 1].map(|k| {
+            let repr = reprs[k].as_ref();
+            (carries[k] + bit(repr, i) + 2 * bit(repr, i + 1) + 4 * bit(repr, i + 2)) % 8
+        });
+
+        let mut digits = [0i8; 2];
+        for k in 0..2 {
+            if l[k] % 2 == 1 {
+                digits[k] = if l[k] % 4 == 1 { 1 } else { -1 };
+                // Choose the sign that makes the next position of both scalars zero.
+                if (l[k] == 3 || l[k] == 5) && l[1 - k] % 4 == 2 {
+                    digits[k] = -digits[k];
+                }
+            }
+            if 2 * carries[k] as i8 == 1 + digits[k] {
+                carries[k] = 1 - carries[k];
+            }
+        }
+        jsf.set(i, digits);
+    }
+
+    jsf
+}
+
+/// An iterator over the joint sparse form of a pair of scalars, returned by
+/// [`joint_sparse_form`].
+#[derive(Clone, Debug)]
+pub struct JointSparseForm<F: PrimeField> {
+    // Bit `i` of `nonzero[k]` is set if digit `i` of scalar `k` is non-zero, and bit `i`
+    // of `negative[k]` is set if it is negative.
+    nonzero: [F::Repr; 2],
+    negative: [F::Repr; 2],
+    // The digits at position `NUM_BITS`, if the reprs have no bit for it.
+    top: (i8, i8),
+    front: usize,
+    back: usize,
+}
+
+impl<F: PrimeField> JointSparseForm<F> {
+    fn set(&mut self, index: usize, digits: [i8; 2]) {
+        if index / 8 >= self.nonzero[0].as_ref().len() {
+            self.top = (digits[0], digits[1]);
+            return;
+        }
+        for (k, digit) in digits.iter().enumerate() {
+            let mask = (*digit != 0) as u8;
+            let sign = (*digit < 0) as u8;
+            self.nonzero[k].as_mut()[index / 8] |= mask << (index % 8);
+            self.negative[k].as_mut()[index / 8] |= sign << (index % 8);
+        }
+    }
+
+    fn get(&self, index: usize) -> (i8, i8) {
+        if index / 8 >= self.nonzero[0].as_ref().len() {
+            return self.top;
+        }
+        let digit = |k: usize| {
+            let nonzero = (self.nonzero[k].as_ref()[index / 8] >> (index % 8)) & 1;
+            let negative = (self.negative[k].as_ref()[index / 8] >> (index % 8)) & 1;
+            nonzero as i8 * (1 - 2 * negative as i8)
+        };
+        (digit(0), digit(1))
+    }
+}
+
+impl<F: PrimeField> Iterator for JointSparseForm<F> {
+    type Item = (i8, i8);
+
+    fn next(&mut self) -> Option<(i8, i8)> {
+        if self.front == self.back {
+            return None;
+        }
+        let digits = self.get(self.front);
+        self.front += 1;
+        Some(digits)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<F: PrimeField> DoubleEndedIterator for JointSparseForm<F> {
+    fn next_back(&mut self) -> Option<(i8, i8)> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.get(self.back))
+    }
+}
+
+impl<F: PrimeField> ExactSizeIterator for JointSparseForm<F> {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec::Vec;
+    use ff::{Field, PrimeField};
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    use super::{batch_invert, joint_sparse_form, scalar_windows};
+
+    /// The scalar field of BLS12-381.
+    #[derive(PrimeField)]
+    #[PrimeFieldModulus = "52435875175126190479447740508185965837690552500527637822603658699938581184513"]
+    #[PrimeFieldGenerator = "7"]
+    #[PrimeFieldReprEndianness = "little"]
+    struct Fr([u64; 4]);
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn batch_invert_matches_invert() {
+        let mut rng = rng();
+
+        batch_invert::<Fr>(&mut []);
+
+        for len in [1, 2, 10, 100].iter() {
+            let mut v = (0..*len).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+            v[0] = Fr::ZERO;
+            v[len / 2] = Fr::ONE;
+
+            let expected = v
+                .iter()
+                .map(|e| e.invert().unwrap_or(Fr::ZERO))
+                .collect::<Vec<_>>();
+
+            batch_invert(&mut v);
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn scalar_windows_recompose() {
+        let mut rng = rng();
+
+        let num_bits = Fr::NUM_BITS as usize;
+        for window in 1..=64 {
+            let num_windows = (num_bits + window - 1) / window;
+            let top_len = num_bits - (num_windows - 1) * window;
+
+            let mut scalars = vec![Fr::ZERO, Fr::ONE, -Fr::ONE];
+            scalars.extend((0..10).map(|_| Fr::random(&mut rng)));
+            for scalar in scalars {
+                let windows = scalar_windows(&scalar, window);
+                assert_eq!(windows.len(), num_windows);
+
+                let windows = windows.collect::<Vec<_>>();
+                let mut reversed = scalar_windows(&scalar, window).rev().collect::<Vec<_>>();
+                reversed.reverse();
+                assert_eq!(windows, reversed);
+
+                // Every window fits in its width, including the shorter top window.
+                for w in &windows[..num_windows - 1] {
+                    assert!(window == 64 || *w < 1 << window);
+                }
+                assert!(top_len == 64 || windows[num_windows - 1] < 1 << top_len);
+
+                // The windows recompose to the scalar.
+                let recomposed = windows.iter().rev().fold(Fr::ZERO, |acc, w| {
+                    (0..window).fold(acc, |acc, _| acc.double()) + Fr::from(*w)
+                });
+                assert_eq!(recomposed, scalar);
+            }
+        }
+
+        // The first window of one is one, and the windows of zero are zero.
+        assert_eq!(scalar_windows(&Fr::ONE, 4).next(), Some(1));
+        assert!(scalar_windows(&Fr::ZERO, 5).all(|w| w == 0));
+    }
+
+    #[test]
+    fn joint_sparse_form_properties() {
+        let mut rng = rng();
+
+        let mut pairs = vec![
+            (Fr::ZERO, Fr::ZERO),
+            (Fr::ONE, Fr::ZERO),
+            (Fr::ZERO, -Fr::ONE),
+            (-Fr::ONE, -Fr::ONE),
+        ];
+        for _ in 0..50 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            pairs.extend_from_slice(&[(a, b), (a, a), (a, -a), (a, Fr::ZERO)]);
+        }
+
+        for (a, b) in pairs {
+            let jsf = joint_sparse_form(&a, &b);
+            assert_eq!(jsf.len(), Fr::NUM_BITS as usize + 1);
+
+            let digits = jsf.collect::<Vec<_>>();
+            let mut reversed = joint_sparse_form(&a, &b).rev().collect::<Vec<_>>();
+            reversed.reverse();
+            assert_eq!(digits, reversed);
+
+            // The digits recompose to the scalars.
+            let recompose = |row: fn(&(i8, i8)) -> i8| {
+                digits.iter().rev().fold(Fr::ZERO, |acc, d| match row(d) {
+                    1 => acc.double() + Fr::ONE,
+                    -1 => acc.double() - Fr::ONE,
+                    _ => acc.double(),
+                })
+            };
+            assert_eq!(recompose(|d| d.0), a);
+            assert_eq!(recompose(|d| d.1), b);
+
+            // Of any three consecutive positions, at least one is zero in both rows.
+            for w in digits.windows(3) {
+                assert!(w.contains(&(0, 0)));
+            }
+
+            for w in digits.windows(2) {
+                let rows = [
+                    (w[0].0, w[1].0, w[0].1, w[1].1),
+                    (w[0].1, w[1].1, w[0].0, w[1].0),
+                ];
+                for &(lo, hi, other_lo, other_hi) in &rows {
+                    // Adjacent digits in a row do not have opposite signs.
+                    assert_ne!(lo * hi, -1);
+                    // Adjacent non-zero digits in a row are matched by a non-zero digit above
+                    // and a zero digit below in the other row.
+                    if lo * hi != 0 {
+                        assert_ne!(other_hi, 0);
+                        assert_eq!(other_lo, 0);
+                    }
+                }
+            }
+        }
+    }
+}
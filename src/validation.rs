@@ -0,0 +1,99 @@
+//! Diagnostics for rejected point encodings.
+
+use core::fmt;
+
+use crate::cofactor::CofactorGroup;
+
+/// A report of which validity checks an encoded point passes.
+///
+/// The [`GroupEncoding`](crate::GroupEncoding) APIs only report whether an encoding is
+/// valid, as a constant-time [`Choice`](subtle::Choice). When debugging interoperability issues or auditing
+/// deployed data, it is more useful to know *why* an encoding was rejected; that is what
+/// this report provides.
+///
+/// The encoding is decoded with
+/// [`GroupEncoding::from_bytes_on_curve_only`](crate::GroupEncoding::from_bytes_on_curve_only),
+/// so curves whose implementation of that method also checks subgroup membership report
+/// points outside the subgroup as not being on the curve. Checks that depend on the
+/// encoding decoding to a point on the curve are `None` if it does not. Producing a
+/// report is not constant time, and it should not be used on secret data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Whether the encoding decodes to a point on the curve.
+    pub on_curve: bool,
+    /// Whether the point is in the prime-order subgroup.
+    pub in_subgroup: Option<bool>,
+    /// Whether the encoding is the canonical encoding of the point.
+    pub canonical: Option<bool>,
+    /// Whether the point is not the identity.
+    pub not_identity: Option<bool>,
+}
+
+impl ValidationReport {
+    /// Runs all of the validity checks on the given encoding.
+    pub fn new<G: CofactorGroup>(repr: &G::Repr) -> Self {
+        match Option::<G>::from(G::from_bytes_on_curve_only(repr)) {
+            Some(p) => ValidationReport {
+                on_curve: true,
+                in_subgroup: Some(p.is_torsion_free().into()),
+                canonical: Some(p.to_bytes().as_ref() == repr.as_ref()),
+                not_identity: Some((!p.is_identity()).into()),
+            },
+            None => ValidationReport {
+                on_curve: false,
+                in_subgroup: None,
+                canonical: None,
+                not_identity: None,
+            },
+        }
+    }
+
+    /// Returns `true` if every check passed.
+    pub fn is_valid(&self) -> bool {
+        self.on_curve
+            && self.in_subgroup == Some(true)
+            && self.canonical == Some(true)
+            && self.not_identity == Some(true)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.on_curve {
+            return f.write_str("the encoding does not decode to a point on the curve");
+        }
+        if self.is_valid() {
+            return f.write_str("the encoding is valid");
+        }
+
+        let failures = [
+            (
+                self.in_subgroup,
+                "the point is not in the prime-order subgroup",
+            ),
+            (self.canonical, "the encoding is not canonical"),
+            (self.not_identity, "the point is the identity"),
+        ];
+        let mut first = true;
+        for (_, message) in failures.iter().filter(|(passed, _)| *passed == Some(false)) {
+            if !first {
+                f.write_str("; ")?;
+            }
+            f.write_str(message)?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the checks of a [`ValidationReport`] on an element.
+pub trait Validate {
+    /// Returns a report of which validity checks the encoding of this element passes.
+    fn validate(&self) -> ValidationReport;
+}
+
+impl<G: CofactorGroup> Validate for G {
+    fn validate(&self) -> ValidationReport {
+        ValidationReport::new::<G>(&self.to_bytes())
+    }
+}
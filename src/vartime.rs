@@ -0,0 +1,61 @@
+//! Variable-time conversions from this crate's constant-time return types.
+//!
+//! Most fallible APIs in this crate return a [`CtOption`] or a [`Choice`], so that code
+//! handling secret data does not branch on the result. Application code that handles
+//! only public data (parsing public keys, checking signatures) ends up converting these
+//! back to `bool` and `Option` anyway; the extension traits in this module make that
+//! conversion explicit, and easy to find in review.
+//!
+//! ```
+//! use group::vartime::CtOptionExt;
+//! use subtle::{Choice, CtOption};
+//!
+//! let parsed = CtOption::new(7u8, Choice::from(1));
+//! assert_eq!(parsed.expect_vartime("valid public input"), 7);
+//! ```
+//!
+//! None of these methods may be used on results that depend on secret data.
+
+use subtle::{Choice, CtOption};
+
+/// Variable-time conversions from [`CtOption`].
+pub trait CtOptionExt<T> {
+    /// Converts this into an [`Option`], branching on whether it is `Some`.
+    fn into_option_vartime(self) -> Option<T>;
+
+    /// Converts this into a [`Result`], mapping `None` to `Err(err)`.
+    fn ok_or_vartime<E>(self, err: E) -> Result<T, E>;
+
+    /// Returns the contained value.
+    ///
+    /// # Panics
+    ///
+    /// Panics with `msg` if this is `None`.
+    fn expect_vartime(self, msg: &str) -> T;
+}
+
+impl<T> CtOptionExt<T> for CtOption<T> {
+    fn into_option_vartime(self) -> Option<T> {
+        self.into()
+    }
+
+    fn ok_or_vartime<E>(self, err: E) -> Result<T, E> {
+        self.into_option_vartime().ok_or(err)
+    }
+
+    fn expect_vartime(self, msg: &str) -> T {
+        self.into_option_vartime().expect(msg)
+    }
+}
+
+/// Variable-time conversions from [`Choice`].
+pub trait ChoiceExt {
+    /// Converts this into a `bool`.
+    fn into_bool_vartime(self) -> bool;
+}
+
+impl ChoiceExt for Choice {
+    fn into_bool_vartime(self) -> bool {
+        self.into()
+    }
+}
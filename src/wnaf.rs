@@ -1,11 +1,10 @@
 use alloc::vec::Vec;
-use core::iter;
 use core::marker::PhantomData;
-use core::ops::Mul;
+use core::ops::{AddAssign, Mul, SubAssign};
 
 use ff::PrimeField;
 
-use super::Group;
+use super::{Curve, Group, GroupCore};
 
 /// Extension trait on a [`Group`] that provides helpers used by [`Wnaf`].
 pub trait WnafGroup: Group {
@@ -14,17 +13,37 @@ pub trait WnafGroup: Group {
     fn recommended_wnaf_for_num_scalars(num_scalars: usize) -> usize;
 }
 
+/// Recommends a wNAF window size given the number of scalars you intend to multiply a
+/// base by, by minimizing the cost estimated from [`Curve::ADD_COST`] and
+/// [`Curve::DOUBLE_COST`]. Always returns a number between 2 and 22, inclusive.
+///
+/// This can be used to implement [`WnafGroup::recommended_wnaf_for_num_scalars`].
+pub fn wnaf_window_from_costs<C: Curve>(num_scalars: usize) -> usize {
+    let bits = C::Scalar::NUM_BITS as usize;
+    let cost = |window: usize| {
+        // The table takes one doubling and 2^(w - 1) - 1 additions.
+        let table = C::DOUBLE_COST + ((1 << (window - 1)) - 1) * C::ADD_COST;
+        // Each scalar takes a doubling per bit, and an addition per w + 1 bits on average.
+        let per_scalar = bits * C::DOUBLE_COST + bits / (window + 1) * C::ADD_COST;
+        table.saturating_add(num_scalars.saturating_mul(per_scalar))
+    };
+
+    (2..=22).min_by_key(|&window| cost(window)).unwrap()
+}
+
 /// Replaces the contents of `table` with a w-NAF window table for the given window size.
 pub(crate) fn wnaf_table<G: Group>(table: &mut Vec<G>, mut base: G, window: usize) {
     table.truncate(0);
     table.reserve(1 << (window - 1));
 
     let dbl = base.double();
+    trace!(DOUBLINGS);
 
     for _ in 0..(1 << (window - 1)) {
         table.push(base);
         base.add_assign(&dbl);
     }
+    trace!(ADDITIONS, 1 << (window - 1));
 }
 
 /// This struct represents a view of a sequence of bytes as a sequence of
@@ -140,7 +159,7 @@ pub(crate) fn wnaf_form<S: AsRef<[u8]>>(wnaf: &mut Vec<i64>, c: S, window: usize
                 carry = 1;
                 (window_val as i64).wrapping_sub(width as i64)
             });
-            wnaf.extend(iter::repeat(0).take(window - 1));
+            wnaf.resize(wnaf.len() + window - 1, 0);
             pos += window;
         }
     }
@@ -151,6 +170,18 @@ pub(crate) fn wnaf_form<S: AsRef<[u8]>>(wnaf: &mut Vec<i64>, c: S, window: usize
 /// This function must be provided a `table` and `wnaf` that were constructed with
 /// the same window size; otherwise, it may panic or produce invalid results.
 pub(crate) fn wnaf_exp<G: Group>(table: &[G], wnaf: &[i64]) -> G {
+    wnaf_exp_mixed(table, wnaf)
+}
+
+/// Performs w-NAF exponentiation with a window table whose entries may be in a different
+/// representation from the result, such as the affine representation of a curve.
+///
+/// This function must be provided a `table` and `wnaf` that were constructed with
+/// the same window size; otherwise, it may panic or produce invalid results.
+pub(crate) fn wnaf_exp_mixed<G, T>(table: &[T], wnaf: &[i64]) -> G
+where
+    G: Group + for<'r> AddAssign<&'r T> + for<'r> SubAssign<&'r T>,
+{
     let mut result = G::identity();
 
     let mut found_one = false;
@@ -158,6 +189,7 @@ pub(crate) fn wnaf_exp<G: Group>(table: &[G], wnaf: &[i64]) -> G {
     for n in wnaf.iter().rev() {
         if found_one {
             result = result.double();
+            trace!(DOUBLINGS);
         }
 
         if *n != 0 {
@@ -168,6 +200,7 @@ pub(crate) fn wnaf_exp<G: Group>(table: &[G], wnaf: &[i64]) -> G {
             } else {
                 result -= &table[((-n) / 2) as usize];
             }
+            trace!(ADDITIONS);
         }
     }
 
@@ -266,6 +299,12 @@ impl<G: Group> Wnaf<(), Vec<G>, Vec<i64>> {
     }
 }
 
+impl<G: Group> Default for Wnaf<(), Vec<G>, Vec<i64>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(feature = "wnaf-memuse")]
 impl<G: Group + memuse::DynamicUsage> memuse::DynamicUsage for Wnaf<(), Vec<G>, Vec<i64>> {
     fn dynamic_usage(&self) -> usize {
@@ -304,7 +343,10 @@ impl<G: WnafGroup> Wnaf<(), Vec<G>, Vec<i64>> {
 
     /// Given a scalar, compute its wNAF representation and return a `Wnaf` object that can perform
     /// exponentiations with `.base(..)`.
-    pub fn scalar(&mut self, scalar: &<G as Group>::Scalar) -> Wnaf<usize, &mut Vec<G>, &[i64]> {
+    pub fn scalar(
+        &mut self,
+        scalar: &<G as GroupCore>::Scalar,
+    ) -> Wnaf<usize, &mut Vec<G>, &[i64]> {
         // We hard-code a window size of 4.
         let window_size = 4;
 
@@ -334,7 +376,7 @@ impl<'a, G: Group> Wnaf<usize, &'a [G], &'a mut Vec<i64>> {
 }
 
 #[cfg(feature = "wnaf-memuse")]
-impl<'a, G: Group> memuse::DynamicUsage for Wnaf<usize, &'a [G], Vec<i64>> {
+impl<G: Group> memuse::DynamicUsage for Wnaf<usize, &[G], Vec<i64>> {
     fn dynamic_usage(&self) -> usize {
         // The heap memory for the window table is counted in the parent `Wnaf`.
         self.scalar.dynamic_usage()
@@ -359,7 +401,7 @@ impl<'a, G: Group> Wnaf<usize, &'a mut Vec<G>, &'a [i64]> {
 }
 
 #[cfg(feature = "wnaf-memuse")]
-impl<'a, G: Group + memuse::DynamicUsage> memuse::DynamicUsage for Wnaf<usize, Vec<G>, &'a [i64]> {
+impl<G: Group + memuse::DynamicUsage> memuse::DynamicUsage for Wnaf<usize, Vec<G>, &[i64]> {
     fn dynamic_usage(&self) -> usize {
         // The heap memory for the scalar representation is counted in the parent `Wnaf`.
         self.base.dynamic_usage()
@@ -383,7 +425,7 @@ impl<B, S: AsRef<[i64]>> Wnaf<usize, B, S> {
 
 impl<B, S: AsMut<Vec<i64>>> Wnaf<usize, B, S> {
     /// Performs exponentiation given a scalar.
-    pub fn scalar<G: Group>(&mut self, scalar: &<G as Group>::Scalar) -> G
+    pub fn scalar<G: Group>(&mut self, scalar: &<G as GroupCore>::Scalar) -> G
     where
         B: AsRef<[G]>,
     {
@@ -426,9 +468,21 @@ impl<F: PrimeField, const WINDOW_SIZE: usize> WnafScalar<F, WINDOW_SIZE> {
 
         WnafScalar {
             wnaf,
-            field: PhantomData::default(),
+            field: PhantomData,
         }
     }
+
+    /// Multiplies the given base by this scalar.
+    ///
+    /// A window table for `base` is computed on the fly and discarded afterwards, but the
+    /// scalar's w-NAF form is reused, so this is suited to multiplying a single scalar
+    /// (such as a verifier challenge) by many different bases. Bases that are multiplied
+    /// by several scalars should instead be precomputed with [`WnafBase`].
+    pub fn base<G: Group<Scalar = F>>(&self, base: G) -> G {
+        let mut table = vec![];
+        wnaf_table(&mut table, base, WINDOW_SIZE);
+        wnaf_exp(&table, &self.wnaf)
+    }
 }
 
 /// A fixed window table for a group element, precomputed to improve the speed of scalar
@@ -493,6 +547,24 @@ impl<G: Group, const WINDOW_SIZE: usize> WnafBase<G, WINDOW_SIZE> {
 
         WnafBase { table }
     }
+
+    /// Multiplies this base by the given scalar.
+    ///
+    /// The scalar's w-NAF form is computed on the fly and discarded afterwards. This only
+    /// borrows the window table immutably, so a single `WnafBase` can be built once and
+    /// then shared by reference between threads:
+    ///
+    /// ```ignore
+    /// use rayon::prelude::*;
+    ///
+    /// let wnaf_base = WnafBase::<_, 8>::new(generator);
+    /// let results: Vec<_> = scalars.par_iter().map(|s| wnaf_base.scalar(s)).collect();
+    /// ```
+    pub fn scalar(&self, scalar: &G::Scalar) -> G {
+        let mut wnaf = vec![];
+        wnaf_form(&mut wnaf, scalar.to_repr(), WINDOW_SIZE);
+        wnaf_exp(&self.table, &wnaf)
+    }
 }
 
 impl<G: Group, const WINDOW_SIZE: usize> Mul<&WnafScalar<G::Scalar, WINDOW_SIZE>>
@@ -504,3 +576,68 @@ impl<G: Group, const WINDOW_SIZE: usize> Mul<&WnafScalar<G::Scalar, WINDOW_SIZE>
         wnaf_exp(&self.table, &rhs.wnaf)
     }
 }
+
+/// A fixed window table for a curve point, stored in affine form.
+///
+/// This behaves like [`WnafBase`], but the window table is converted to the affine
+/// representation with a single call to [`Curve::batch_normalize`] when it is computed.
+/// Each addition during scalar multiplication is then a mixed addition, which is cheaper
+/// than the full addition used by `WnafBase` for most curves (see
+/// [`Curve::MIXED_ADD_COST`]). This is worthwhile for long-lived bases that are
+/// multiplied by many scalars.
+///
+/// `AffineWnafBase` is used with [`WnafScalar`] in the same way as `WnafBase`.
+#[derive(Clone, Debug)]
+pub struct AffineWnafBase<C: Curve, const WINDOW_SIZE: usize> {
+    table: Vec<C::AffineRepr>,
+}
+
+#[cfg(feature = "wnaf-memuse")]
+impl<C: Curve, const WINDOW_SIZE: usize> memuse::DynamicUsage for AffineWnafBase<C, WINDOW_SIZE>
+where
+    C::AffineRepr: memuse::DynamicUsage,
+{
+    fn dynamic_usage(&self) -> usize {
+        self.table.dynamic_usage()
+    }
+
+    fn dynamic_usage_bounds(&self) -> (usize, Option<usize>) {
+        self.table.dynamic_usage_bounds()
+    }
+}
+
+impl<C: Curve, const WINDOW_SIZE: usize> AffineWnafBase<C, WINDOW_SIZE>
+where
+    C::AffineRepr: Clone,
+{
+    /// Computes a window table for the given base with the specified `WINDOW_SIZE`, and
+    /// converts it to affine form.
+    pub fn new(base: C) -> Self {
+        let mut projective = vec![];
+        wnaf_table(&mut projective, base, WINDOW_SIZE);
+
+        AffineWnafBase {
+            table: C::batch_normalize_vec(&projective),
+        }
+    }
+
+    /// Multiplies this base by the given scalar.
+    ///
+    /// The scalar's w-NAF form is computed on the fly and discarded afterwards. As with
+    /// [`WnafBase::scalar`], this only borrows the window table immutably.
+    pub fn scalar(&self, scalar: &C::Scalar) -> C {
+        let mut wnaf = vec![];
+        wnaf_form(&mut wnaf, scalar.to_repr(), WINDOW_SIZE);
+        wnaf_exp_mixed(&self.table, &wnaf)
+    }
+}
+
+impl<C: Curve, const WINDOW_SIZE: usize> Mul<&WnafScalar<C::Scalar, WINDOW_SIZE>>
+    for &AffineWnafBase<C, WINDOW_SIZE>
+{
+    type Output = C;
+
+    fn mul(self, rhs: &WnafScalar<C::Scalar, WINDOW_SIZE>) -> Self::Output {
+        wnaf_exp_mixed(&self.table, &rhs.wnaf)
+    }
+}